@@ -0,0 +1,78 @@
+// Generated by intcode-compile, do not edit.
+
+#![allow(dead_code, unused_assignments, unused_mut, unreachable_code, clippy::all)]
+
+use advent_of_code_2019::intcode::{Engine, FastMachine, IntCodeError};
+
+/// The words holding compiled instructions.
+const CODE: &[(i64, i64)] = &[(0, 20)];
+
+fn in_code(address: i64) -> bool {
+    CODE.iter().any(|&(start, end)| start <= address && address < end)
+}
+
+pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError> {
+    if m.is_halted() {
+        return Ok(());
+    }
+    let mut pc = m.pc();
+    let mut rb = m.relative_base();
+    'run: loop {
+        match pc {
+            0 => {
+                // 0: IN   [20]
+                pc = 0;
+                let value = match m.pop_input() {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(20, value);
+                pc = 2;
+                continue 'run;
+            }
+            2 => {
+                // 2: MUL  [20], [20], [21]
+                pc = 2;
+                let value = match i64::checked_mul(m.get(20), m.get(20)) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(21, value);
+                // 6: ADD  [22], [21], [22]
+                pc = 6;
+                let value = match i64::checked_add(m.get(22), m.get(21)) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(22, value);
+                // 10: ADD  [20], #-1, [20]
+                pc = 10;
+                let value = match i64::checked_add(m.get(20), -1) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(20, value);
+                // 14: JT   [20], #2
+                pc = 14;
+                if m.get(20) != 0 {
+                    pc = 2;
+                    continue 'run;
+                }
+                pc = 17;
+                continue 'run;
+            }
+            17 => {
+                // 17: OUT  [22]
+                pc = 17;
+                m.push_output(m.get(22));
+                // 19: HLT
+                pc = 19;
+                break 'run;
+            }
+            _ => break 'run,
+        }
+    }
+    m.set_pc(pc);
+    m.set_relative_base(rb);
+    m.run()
+}
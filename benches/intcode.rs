@@ -0,0 +1,142 @@
+//! Compares the `Machine` interpreter with the `FastMachine` engine and with
+//! code generated by `intcode-compile`.
+//!
+//! Run with `cargo bench --bench intcode`.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{asm, Engine, FastMachine, Machine};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// `SQUARES` compiled with `intcode-compile`.
+#[rustfmt::skip]
+#[path = "compiled/squares.rs"]
+mod squares;
+
+/// Number of times each workload is timed; the fastest time is reported.
+const SAMPLES: usize = 5;
+
+/// Adds up the squares of `n` down to 1: one long-running hot loop.
+const SQUARES: &str = "
+        IN   [n]
+loop:   MUL  [n], [n], [square]
+        ADD  [sum], [square], [sum]
+        ADD  [n], #-1, [n]
+        JT   [n], #loop
+        OUT  [sum]
+        HLT
+n:      .data 0
+square: .data 0
+sum:    .data 0
+";
+
+/// The day 5 comparison example: outputs 999, 1000 or 1001 depending on
+/// whether the input is below, equal to or above 8.
+const COMPARE: [i64; 47] = [
+    3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+    1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105,
+    1, 46, 98, 99,
+];
+
+/// A program that rewrites its own operand on every iteration, so the fast
+/// engine keeps dropping and decoding the instruction.
+const COUNTDOWN: [i64; 10] = [104, 100_000, 1001, 1, -1, 1, 1005, 1, 0, 99];
+
+fn time(f: &mut dyn FnMut() -> i64) -> (Duration, i64) {
+    let mut best = Duration::MAX;
+    let mut result = 0;
+    for _ in 0..SAMPLES {
+        let start = Instant::now();
+        result = black_box(f());
+        best = best.min(start.elapsed());
+    }
+    (best, result)
+}
+
+/// Times every engine and prints how much faster each is than the first.
+fn compare(name: &str, engines: &mut [(&str, &mut dyn FnMut() -> i64)]) {
+    let mut baseline = None;
+    for (engine, run) in engines.iter_mut() {
+        let (elapsed, result) = time(run);
+        let (base_time, base_result) = *baseline.get_or_insert((elapsed, result));
+        assert_eq!(result, base_result, "{}: {} disagrees", name, engine);
+
+        println!(
+            "{:<12} {:<12} {:>10.2?}  speedup {:>5.1}x",
+            name,
+            engine,
+            elapsed,
+            base_time.as_secs_f64() / elapsed.as_secs_f64()
+        );
+    }
+}
+
+fn main() {
+    let squares = asm::assemble(SQUARES).unwrap();
+    compare(
+        "hot loop",
+        &mut [
+            ("Machine", &mut || {
+                let mut machine = Machine::new(&squares[..]);
+                machine.push_input(1_000_000);
+                machine.run().unwrap();
+                machine.pop_output().unwrap()
+            }),
+            ("FastMachine", &mut || {
+                let mut machine = FastMachine::new(&squares[..]);
+                machine.push_input(1_000_000);
+                machine.run().unwrap();
+                machine.pop_output().unwrap()
+            }),
+            ("compiled", &mut || {
+                let mut machine = FastMachine::new(&squares[..]);
+                machine.push_input(1_000_000);
+                squares::run(&mut machine).unwrap();
+                machine.pop_output().unwrap()
+            }),
+        ],
+    );
+
+    compare(
+        "short runs",
+        &mut [
+            ("Machine", &mut || {
+                (0..20_000)
+                    .map(|input| {
+                        let mut machine = Machine::new(&COMPARE[..]);
+                        machine.push_input(input % 16);
+                        machine.run().unwrap();
+                        machine.pop_output().unwrap()
+                    })
+                    .sum()
+            }),
+            ("FastMachine", &mut || {
+                (0..20_000)
+                    .map(|input| {
+                        let mut machine = FastMachine::new(&COMPARE[..]);
+                        machine.push_input(input % 16);
+                        machine.run().unwrap();
+                        machine.pop_output().unwrap()
+                    })
+                    .sum()
+            }),
+        ],
+    );
+
+    compare(
+        "self-modify",
+        &mut [
+            ("Machine", &mut || {
+                let mut machine = Machine::new(&COUNTDOWN[..]);
+                machine.run().unwrap();
+                machine.take_output().len() as i64
+            }),
+            ("FastMachine", &mut || {
+                let mut machine = FastMachine::new(&COUNTDOWN[..]);
+                machine.run().unwrap();
+                machine.take_output().len() as i64
+            }),
+        ],
+    );
+}
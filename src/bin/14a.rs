@@ -1,49 +1,29 @@
 extern crate advent_of_code_2019;
 
+use advent_of_code_2019::days::day14::Day14;
 use advent_of_code_2019::parser;
-use advent_of_code_2019::nanofactory::{NanoFactory, Reaction, Term};
-use std::collections::{HashSet};
+use advent_of_code_2019::solver::Solver;
 use std::env;
-
+use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     parser::print_args(&args);
 
-    let lines = parser::parse_lines(&args);
-
-    let reactions: HashSet<Reaction> = lines.into_iter().map(|l| parse_reaction(l)).collect();
-
-    println!("{:?}", reactions);
-
-    let fuel = Term {
-        chemical: String::from("FUEL"),
-        amount: 1,
-    };
-
-    let factory = NanoFactory {
-        reactions: reactions
+    let input = match parser::parse_input(&args, 14) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
     };
 
-    let result = factory.produce(1, &fuel, HashSet::new()).unwrap();
-
-    println!("RESULT:");
-    println!("{:?}", result.0);
-}
-
-fn parse_term(term: &str) -> Term {
-    let (num, chem) = term.split_at(term.find(' ').unwrap());
-    Term {
-        chemical: String::from(chem.trim()),
-        amount: num.parse().unwrap(),
-    }
-}
-
-fn parse_reaction(line: String) -> Reaction {
-    let sides: Vec<&str> = line.splitn(2, " => ").collect();
-    Reaction {
-        to: parse_term(sides[1]),
-        from: sides[0].split(", ").map(|s| parse_term(s)).collect(),
+    match Day14.part1(&input) {
+        Ok(answer) => println!("ORE needed: {}", answer),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
     }
 }
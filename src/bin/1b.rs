@@ -1,24 +1,29 @@
 extern crate advent_of_code_2019;
 
-use advent_of_code_2019::{parser, sum};
+use advent_of_code_2019::days::day01::Day01;
+use advent_of_code_2019::parser;
+use advent_of_code_2019::solver::Solver;
 use std::env;
+use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     parser::print_args(&args);
 
-    let modules = parser::parse_lines(&args);
+    let input = match parser::parse_input(&args, 1) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
 
-    println!(
-        "Fuel needed: {}",
-        sum::sum_fn_parsed(modules.iter(), fuel_needed)
-    );
-}
-
-fn fuel_needed(weight: i32) -> i32 {
-    match weight / 3 - 2 {
-        x if x < 1 => 0,
-        x => x + fuel_needed(x),
+    match Day01.part2(&input) {
+        Ok(answer) => println!("Fuel needed: {}", answer),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
     }
 }
@@ -1,26 +1,29 @@
 extern crate advent_of_code_2019;
 
-use advent_of_code_2019::{parser, wires};
+use advent_of_code_2019::days::day03::Day03;
+use advent_of_code_2019::parser;
+use advent_of_code_2019::solver::Solver;
 use std::env;
+use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     parser::print_args(&args);
 
-    let lines = parser::parse_lines(&args);
+    let input = match parser::parse_input(&args, 3) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
 
-    let coords = wires::get_wires(lines);
-
-    let result = coords[0]
-        .iter()
-        .filter(|i| coords[1].contains(i))
-        .min_by_key(|p| p.dist())
-        .unwrap();
-
-    println!(
-        "Closest intersection is at {:?}, Manhattan Distance: {}",
-        result,
-        result.dist()
-    );
+    match Day03.part1(&input) {
+        Ok(answer) => println!("Closest intersection, Manhattan Distance: {}", answer),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
 }
@@ -1,28 +1,29 @@
-use advent_of_code_2019::{parser, wires};
-use std::collections::HashMap;
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::days::day03::Day03;
+use advent_of_code_2019::parser;
+use advent_of_code_2019::solver::Solver;
 use std::env;
+use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     parser::print_args(&args);
 
-    let lines = parser::parse_lines(&args);
-
-    let coords = wires::get_wires(lines);
-
-    let first: HashMap<_, _> = coords[0].iter().map(|i| (i, i.path())).collect();
-    let second: HashMap<_, _> = coords[1].iter().map(|i| (i, i.path())).collect();
-
-    let result = coords[0]
-        .iter()
-        .filter(|i| coords[1].contains(i))
-        .min_by_key(|p| first.get(p).unwrap() + second.get(p).unwrap())
-        .unwrap();
+    let input = match parser::parse_input(&args, 3) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
 
-    println!(
-        "Closest intersection is at {:?}, Path distance: {}",
-        result,
-        first.get(result).unwrap() + second.get(result).unwrap()
-    );
+    match Day03.part2(&input) {
+        Ok(answer) => println!("Closest intersection, Path distance: {}", answer),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
 }
@@ -1,31 +1,29 @@
-use advent_of_code_2019::{parser, password};
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::days::day04::Day04;
+use advent_of_code_2019::parser;
+use advent_of_code_2019::solver::Solver;
 use std::env;
+use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     parser::print_args(&args);
 
-    let range: Vec<&str> = args[1].split('-').map(|s| s.trim()).collect();
-    let (min, max) = (
-        range[0].parse::<i32>().unwrap(),
-        range[1].parse::<i32>().unwrap(),
-    );
-
-    let mut counter = 0;
-    let mut current = password::Password::new(min);
-    while current.value() <= max {
-        if current.is_strictly_valid() {
-            counter += 1;
+    let input = match parser::parse_input(&args, 4) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
         }
-        match current.next() {
-            Some(p) => current = p,
-            _ => break,
+    };
+
+    match Day04.part2(&input) {
+        Ok(answer) => println!("\"Strictly\" valid passwords within range: {}", answer),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
         }
     }
-
-    println!(
-        "\"Strictly\" valid passwords within range ({} - {}): {}",
-        min, max, counter
-    );
 }
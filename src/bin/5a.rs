@@ -1,36 +1,32 @@
 extern crate advent_of_code_2019;
 
+use advent_of_code_2019::days::day05;
 use advent_of_code_2019::{intcode, parser};
 use std::env;
-use std::error::Error;
-use std::io;
-use std::io::Write;
+use std::process;
 
+/// Runs the diagnostic for the system ID given after the file, 1 by default.
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     parser::print_args(&args);
 
-    let mut program = program(&args);
+    let (program, extra) = intcode::program_from_args_or_exit(&args, Some(5));
+    let input = extra.first().and_then(|a| a.parse().ok()).unwrap_or(1);
 
-    let mut pc = 0;
-    let mut rb = 0;
-    while let Ok(true) = intcode::process_instruction(&mut program, &mut pc, &mut rb, &mut stdin, &mut intcode::stdout_output) {}
+    let mut machine = intcode::Machine::new(program);
+    machine.instrument_from_env();
 
-    println!("Done: {}", program[0]);
-}
-
-fn stdin() -> Result<i64, Box<dyn Error>> {
-    print!("INPUT: ");
-    io::stdout().flush()?;
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    Ok(input.trim().parse::<i64>()?)
-}
+    let result = day05::run_diagnostic(&mut machine, input);
+    if let Some(stats) = machine.stats() {
+        println!("{}", stats);
+    }
 
-fn program(args: &Vec<String>) -> Vec<i64> {
-    parser::parse_comma_list(args)
-        .into_iter()
-        .map(|s| s.parse::<i64>().unwrap())
-        .collect()
+    match result {
+        Ok(output) => println!("Diagnostic code: {}", output),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
 }
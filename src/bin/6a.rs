@@ -1,30 +1,29 @@
 extern crate advent_of_code_2019;
 
+use advent_of_code_2019::days::day06::Day06;
 use advent_of_code_2019::parser;
-use std::collections::HashMap;
+use advent_of_code_2019::solver::Solver;
 use std::env;
+use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     parser::print_args(&args);
 
-    let edges: HashMap<_,_> = parser::parse_lines(&args)
-        .iter()
-        .map(|l: &String| l.split(')').collect())
-        .map(|a: Vec<&str>| (String::from(a[1]), String::from(a[0])))
-        .collect();
-    
-    let mut count = 0;
-    for key in edges.keys() {
-        let mut item = key;
-        while edges.contains_key(item) {
-            count += 1;
-            item = edges.get(item).unwrap();
+    let input = match parser::parse_input(&args, 6) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
         }
-    }
-
-    println!("{:?}", edges);
+    };
 
-    println!("Total number of orbits: {}", count);
-}
\ No newline at end of file
+    match Day06.part1(&input) {
+        Ok(answer) => println!("Total number of orbits: {}", answer),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Runs the solver for any day with the same arguments:
+//!
+//! ```text
+//! aoc run 7 --part b --input input7.txt
+//! aoc list
+//! ```
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::days;
+use advent_of_code_2019::parser::{self, Source};
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+const USAGE: &str = "\
+Usage:
+  aoc run <day> [--part a|b] [--input <file>] [<value>...]
+  aoc list
+  aoc help
+
+Commands:
+  run     run the solver for a day, part a unless --part is given
+  list    list the implemented days and parts
+  help    show this help
+
+Without --input the values are used as the input, one per line. With
+neither, the input is read from inputs/dayNN.txt. '--input -' reads stdin.";
+
+/// Exit code for a solver that failed.
+const EXIT_FAILURE: i32 = 1;
+/// Exit code for bad arguments.
+const EXIT_USAGE: i32 = 2;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("list") => {
+            list();
+            Ok(0)
+        }
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(0)
+        }
+        Some(other) => Err(format!("Unknown command '{}'", other)),
+        None => Err("Missing command".to_string()),
+    };
+
+    match result {
+        Ok(code) => process::exit(code),
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            process::exit(EXIT_USAGE);
+        }
+    }
+}
+
+fn list() {
+    for day in days::DAYS {
+        if let Some(solver) = days::solver(day) {
+            let parts: Vec<String> = solver.parts().iter().map(char::to_string).collect();
+            println!("Day {:>2}: {}", day, parts.join(", "));
+        }
+    }
+}
+
+/// Runs one solver and returns the exit code.
+fn run(args: &[String]) -> Result<i32, String> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return Ok(0);
+    }
+
+    let day = args
+        .first()
+        .ok_or_else(|| "Missing day".to_string())?
+        .parse::<u32>()
+        .map_err(|_| format!("'{}' is not a day", args[0]))?;
+
+    let mut part = 'a';
+    let mut file: Option<&str> = None;
+    let mut values: Vec<&str> = Vec::new();
+
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                part = match rest.next().map(String::as_str) {
+                    Some("a") => 'a',
+                    Some("b") => 'b',
+                    _ => return Err("--part takes 'a' or 'b'".to_string()),
+                }
+            }
+            "--input" | "-i" => {
+                file = Some(
+                    rest.next()
+                        .ok_or_else(|| "--input takes a file".to_string())?,
+                )
+            }
+            value => values.push(value),
+        }
+    }
+
+    let solver = days::solver(day).ok_or_else(|| format!("Day {} is not implemented", day))?;
+    if !solver.parts().contains(&part) {
+        return Err(format!("Day {} part {} is not implemented", day, part));
+    }
+
+    let source = match file {
+        Some("-") => Source::Stdin,
+        Some(file) => Source::File(PathBuf::from(file)),
+        None if values.is_empty() => Source::File(parser::default_input(day)),
+        None => Source::Inline(values.iter().map(|v| v.to_string()).collect()),
+    };
+    let input = source.read().map_err(|err| err.to_string())?;
+
+    let result = match part {
+        'a' => solver.part1(&input),
+        _ => solver.part2(&input),
+    };
+
+    match result {
+        Ok(answer) => {
+            println!("{}", answer);
+            Ok(0)
+        }
+        Err(err) => {
+            eprintln!("Day {} part {}: {}", day, part, err);
+            Ok(EXIT_FAILURE)
+        }
+    }
+}
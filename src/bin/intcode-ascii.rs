@@ -0,0 +1,67 @@
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::ascii::{self, Lines};
+use advent_of_code_2019::intcode::{self, Engine, IntCodeError, Machine, StepResult};
+use std::env;
+use std::io;
+use std::io::{BufRead, Write};
+use std::process;
+
+/// Runs an ASCII program interactively: its output is printed as text and
+/// every line typed is sent to it. Arguments after the file are sent first,
+/// one line each, e.g. `intcode-ascii -f input25.txt north "take mug"`.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let (program, commands) = intcode::program_from_args_or_exit(&args, None);
+    let mut machine = Machine::new(program);
+    machine.instrument_from_env();
+
+    for command in commands {
+        for value in ascii::encode(command) {
+            machine.push_input(value);
+        }
+    }
+
+    let result = interact(&mut machine);
+    if let Some(stats) = machine.stats() {
+        println!("{}", stats);
+    }
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn interact(machine: &mut Machine) -> Result<(), IntCodeError> {
+    let stdin = io::stdin();
+    let mut lines = Lines::new();
+
+    loop {
+        let state = machine.run_until_input_needed()?;
+        for value in machine.take_output() {
+            lines.push(value);
+        }
+        if state == StepResult::Halted {
+            lines.finish();
+        }
+        while let Some(chunk) = lines.pop() {
+            println!("{}", chunk);
+        }
+        if state == StepResult::Halted {
+            return Ok(());
+        }
+
+        // The prompt, if any, goes on the line being typed.
+        print!("{}", lines.take_partial());
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return Err(IntCodeError::InputExhausted { pc: machine.pc() });
+        }
+        for value in ascii::encode(line.trim_end_matches(&['\r', '\n'][..])) {
+            machine.push_input(value);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::asm;
+use advent_of_code_2019::parser;
+use std::env;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let source = match parser::read_input(&args) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+
+    match asm::assemble(&source) {
+        Ok(words) => println!("{}", asm::to_comma_list(&words)),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{self, compile};
+use std::env;
+use std::process;
+
+/// `--variables=1,2` leaves the words at addresses 1 and 2 patchable.
+const VARIABLES: &str = "--variables=";
+
+fn main() {
+    let (options, args): (Vec<String>, Vec<String>) =
+        env::args().partition(|a| a.starts_with(VARIABLES));
+
+    let mut variables = Vec::new();
+    for option in &options {
+        for address in option[VARIABLES.len()..].split(',') {
+            match address.trim().parse::<usize>() {
+                Ok(address) => variables.push(address),
+                Err(_) => {
+                    eprintln!("Invalid address in {}: '{}'", option, address);
+                    process::exit(2);
+                }
+            }
+        }
+    }
+
+    let (program, _) = intcode::program_from_args_or_exit(&args, None);
+
+    print!("{}", compile::to_rust(&program, &variables));
+}
@@ -0,0 +1,337 @@
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{self, disasm, Machine, StepResult};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::io;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  s, step [n]             execute n instructions (default 1)
+  c, continue             run until a breakpoint, watchpoint, input request or halt
+  b, break <addr>         set a breakpoint
+  w, watch <addr>         stop when the value at <addr> changes
+  d, delete <addr>        remove a breakpoint or watchpoint
+  l, list [addr] [n]      disassemble n instructions from addr (default pc)
+  p, print pc|rb|mem[a]|mem[a..b]
+  set mem[a] <value>      write a memory cell
+  i, input <v> [v...]     queue input values
+  save <file>             write a snapshot of the machine
+  load <file>             restore a snapshot written by 'save'
+  info                    show registers, breakpoints and watchpoints
+  h, help                 show this help
+  q, quit                 exit";
+
+/// Values per line when printing a memory range.
+const ROW: usize = 10;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let (program, _) = intcode::program_from_args_or_exit(&args, None);
+
+    let mut debugger = Debugger::new(Machine::new(program));
+    debugger.show_current();
+
+    let stdin = io::stdin();
+    loop {
+        print!("(intcode) ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+
+        match debugger.command(line.trim()) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(err) => println!("{}", err),
+        }
+    }
+}
+
+struct Debugger {
+    machine: Machine,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeMap<usize, i64>,
+}
+
+impl Debugger {
+    fn new(machine: Machine) -> Self {
+        Debugger {
+            machine,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeMap::new(),
+        }
+    }
+
+    /// Runs one command. Returns `Ok(false)` when the debugger should exit.
+    fn command(&mut self, line: &str) -> Result<bool, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let args = if words.is_empty() {
+            &[][..]
+        } else {
+            &words[1..]
+        };
+
+        match words.first().cloned().unwrap_or("") {
+            "" => {}
+            "s" | "step" => {
+                let n = match args.first() {
+                    Some(n) => parse_number(n)?,
+                    None => 1,
+                };
+                for _ in 0..n {
+                    if !self.step()? {
+                        break;
+                    }
+                }
+                self.show_current();
+            }
+            "c" | "continue" => {
+                // Always move off the current instruction, even if it has a breakpoint.
+                if self.step()? {
+                    while !self.breakpoints.contains(&self.machine.pc()) && self.step()? {}
+                }
+                if self.breakpoints.contains(&self.machine.pc()) {
+                    println!("Breakpoint at {}", self.machine.pc());
+                }
+                self.show_current();
+            }
+            "b" | "break" => {
+                let address = parse_address(args.first())?;
+                self.breakpoints.insert(address);
+                println!("Breakpoint set at {}", address);
+            }
+            "w" | "watch" => {
+                let address = parse_address(args.first())?;
+                self.watchpoints
+                    .insert(address, self.machine.memory()[address]);
+                println!(
+                    "Watching mem[{}] = {}",
+                    address,
+                    self.machine.memory()[address]
+                );
+            }
+            "d" | "delete" => {
+                let address = parse_address(args.first())?;
+                let removed =
+                    self.breakpoints.remove(&address) | self.watchpoints.remove(&address).is_some();
+                if !removed {
+                    return Err(format!("Nothing set at {}", address));
+                }
+            }
+            "l" | "list" => {
+                let mut address = match args.first() {
+                    Some(a) => parse_number(a)?,
+                    None => self.machine.pc(),
+                };
+                let count = match args.get(1) {
+                    Some(n) => parse_number(n)?,
+                    None => 10,
+                };
+                let memory = self.machine.memory();
+                for _ in 0..count {
+                    if address >= memory.len() {
+                        break;
+                    }
+                    let line = disasm::line_at(memory, address);
+                    println!("{}{}", self.marker(address), line);
+                    address = match address.checked_add(line.words.len()) {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+            }
+            "p" | "print" => self.print(args.first().cloned().unwrap_or(""))?,
+            "set" => {
+                let address = parse_cell(args.first().cloned().unwrap_or(""))?;
+                let value = args
+                    .get(1)
+                    .ok_or_else(|| "Usage: set mem[a] <value>".to_string())?
+                    .parse::<i64>()
+                    .map_err(|e| e.to_string())?;
+                self.machine.memory_mut()[address] = value;
+                if let Some(old) = self.watchpoints.get_mut(&address) {
+                    *old = value;
+                }
+            }
+            "i" | "input" => {
+                if args.is_empty() {
+                    return Err("Usage: input <v> [v...]".to_string());
+                }
+                for arg in args {
+                    self.machine
+                        .push_input(arg.parse::<i64>().map_err(|e| e.to_string())?);
+                }
+            }
+            "save" => {
+                let path = args
+                    .first()
+                    .ok_or_else(|| "Usage: save <file>".to_string())?;
+                self.machine
+                    .save_to_file(path)
+                    .map_err(|e| format!("Could not save {}: {}", path, e))?;
+                println!("Saved to {}", path);
+            }
+            "load" => {
+                let path = args
+                    .first()
+                    .ok_or_else(|| "Usage: load <file>".to_string())?;
+                self.machine = Machine::load_from_file(path)
+                    .map_err(|e| format!("Could not load {}: {}", path, e))?;
+                for (address, old) in self.watchpoints.iter_mut() {
+                    *old = self.machine.memory()[*address];
+                }
+                println!("Loaded {}", path);
+                self.show_current();
+            }
+            "info" => {
+                println!(
+                    "pc = {}, rb = {}",
+                    self.machine.pc(),
+                    self.machine.relative_base()
+                );
+                println!("Breakpoints: {:?}", self.breakpoints);
+                println!("Watchpoints: {:?}", self.watchpoints);
+            }
+            "h" | "help" => println!("{}", HELP),
+            "q" | "quit" => return Ok(false),
+            other => return Err(format!("Unknown command '{}', try 'help'", other)),
+        }
+
+        Ok(true)
+    }
+
+    /// Executes one instruction. Returns false if execution should stop, that
+    /// is the machine halted, needs input or a watched cell changed.
+    fn step(&mut self) -> Result<bool, String> {
+        match self.machine.step().map_err(|e| e.to_string())? {
+            StepResult::Continue => {}
+            StepResult::Output(value) => println!("OUTPUT: {}", value),
+            StepResult::NeedInput => {
+                println!(
+                    "Waiting for input at {}, use 'input <v>'",
+                    self.machine.pc()
+                );
+                return Ok(false);
+            }
+            StepResult::Halted => {
+                println!("Halted");
+                return Ok(false);
+            }
+        }
+
+        let mut changed = false;
+        for (address, old) in self.watchpoints.iter_mut() {
+            let new = self.machine.memory()[*address];
+            if new != *old {
+                println!("Watchpoint mem[{}]: {} -> {}", address, old, new);
+                *old = new;
+                changed = true;
+            }
+        }
+
+        Ok(!changed)
+    }
+
+    fn print(&self, what: &str) -> Result<(), String> {
+        match what {
+            "pc" => println!("pc = {}", self.machine.pc()),
+            "rb" => println!("rb = {}", self.machine.relative_base()),
+            _ => {
+                let (from, to) = parse_range(what)?;
+                let memory = self.machine.memory();
+                if to - from == 1 {
+                    println!("mem[{}] = {}", from, memory[from]);
+                    return Ok(());
+                }
+
+                // Ranges stop at the end of memory, and long ones are
+                // printed a row at a time.
+                let to = to.min(memory.len());
+                if from >= to {
+                    return Err(format!(
+                        "mem[{}..] is past the end of memory at {}",
+                        from,
+                        memory.len()
+                    ));
+                }
+                for start in (from..to).step_by(ROW) {
+                    let end = (start + ROW).min(to);
+                    let values: Vec<String> = (start..end).map(|a| memory[a].to_string()).collect();
+                    println!("mem[{}..{}] = {}", start, end, values.join(","));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn show_current(&self) {
+        if !self.machine.is_halted() {
+            let pc = self.machine.pc();
+            println!(
+                "{}{}",
+                self.marker(pc),
+                disasm::line_at(self.machine.memory(), pc)
+            );
+        }
+    }
+
+    fn marker(&self, address: usize) -> &'static str {
+        match (
+            address == self.machine.pc(),
+            self.breakpoints.contains(&address),
+        ) {
+            (true, true) => "*>",
+            (true, false) => " >",
+            (false, true) => "* ",
+            (false, false) => "  ",
+        }
+    }
+}
+
+fn parse_number(s: &str) -> Result<usize, String> {
+    s.parse::<usize>()
+        .map_err(|_| format!("'{}' is not a valid number", s))
+}
+
+fn parse_address(s: Option<&&str>) -> Result<usize, String> {
+    parse_number(s.ok_or_else(|| "Missing address".to_string())?)
+}
+
+/// Parses `mem[a]` into an address.
+fn parse_cell(s: &str) -> Result<usize, String> {
+    if s.contains("..") {
+        return Err(format!("Can only set one address at a time, got '{}'", s));
+    }
+    parse_range(s).map(|(address, _)| address)
+}
+
+/// Parses `mem[a]` or `mem[a..b]` into a half-open range.
+fn parse_range(s: &str) -> Result<(usize, usize), String> {
+    let inner = s
+        .strip_prefix("mem[")
+        .and_then(|r| r.strip_suffix(']'))
+        .ok_or_else(|| format!("Expected pc, rb, mem[a] or mem[a..b], got '{}'", s))?;
+
+    match inner.find("..") {
+        Some(i) => {
+            let (from, to) = (parse_number(&inner[..i])?, parse_number(&inner[i + 2..])?);
+            match from < to {
+                true => Ok((from, to)),
+                false => Err(format!("Empty range {}..{}", from, to)),
+            }
+        }
+        None => {
+            let address = parse_number(inner)?;
+            match address.checked_add(1) {
+                Some(end) => Ok((address, end)),
+                None => Err(format!("Address {} is out of range", address)),
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{self, disasm, Memory};
+use std::env;
+
+const FLOW: &str = "--flow";
+
+fn main() {
+    let (flow, args): (Vec<String>, Vec<String>) = env::args().partition(|a| a == FLOW);
+
+    let (program, _) = intcode::program_from_args_or_exit(&args, None);
+    let program = Memory::from(program);
+
+    let lines = match flow.is_empty() {
+        false => disasm::follow_control_flow(&program),
+        true => disasm::linear(&program),
+    };
+
+    for line in lines {
+        println!("{}", line);
+    }
+}
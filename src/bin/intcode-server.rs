@@ -0,0 +1,59 @@
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::server::Server;
+use advent_of_code_2019::intcode::{self, Machine};
+use std::env;
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::process;
+
+/// `--tcp=127.0.0.1:2019` listens on a TCP address, the default.
+const TCP: &str = "--tcp=";
+/// `--unix=/tmp/intcode.sock` listens on a Unix socket instead.
+const UNIX: &str = "--unix=";
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:2019";
+
+/// Serves a program to every client that connects, each on a machine of its
+/// own: input words are read from the client a line at a time, and outputs
+/// are sent back the same way.
+fn main() {
+    let (options, args): (Vec<String>, Vec<String>) =
+        env::args().partition(|a| a.starts_with(TCP) || a.starts_with(UNIX));
+
+    let (program, _) = intcode::program_from_args_or_exit(&args, None);
+    let server = Server::new(Machine::new(program));
+
+    let result = match options.last().map(String::as_str) {
+        Some(option) if option.starts_with(UNIX) => serve_unix(&server, &option[UNIX.len()..]),
+        Some(option) => serve_tcp(&server, &option[TCP.len()..]),
+        None => serve_tcp(&server, DEFAULT_ADDRESS),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn serve_tcp(server: &Server, address: &str) -> Result<(), String> {
+    let listener = TcpListener::bind(address)
+        .map_err(|err| format!("Can't listen on {}: {}", address, err))?;
+    eprintln!("Listening on {}", address);
+    server.serve_tcp(&listener);
+    Ok(())
+}
+
+#[cfg(unix)]
+fn serve_unix(server: &Server, path: &str) -> Result<(), String> {
+    let listener =
+        UnixListener::bind(path).map_err(|err| format!("Can't listen on {}: {}", path, err))?;
+    eprintln!("Listening on {}", path);
+    server.serve_unix(&listener);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve_unix(_: &Server, _: &str) -> Result<(), String> {
+    Err("Unix sockets are not supported on this platform".to_string())
+}
@@ -0,0 +1,45 @@
+//! Solvers for each day's puzzle.
+
+use crate::solver::Solver;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+
+/// Days that have a solver, in order.
+pub const DAYS: std::ops::RangeInclusive<u32> = 1..=15;
+
+/// The solver for `day` with its default settings.
+pub fn solver(day: u32) -> Option<Box<dyn Solver>> {
+    let solver: Box<dyn Solver> = match day {
+        1 => Box::new(day01::Day01),
+        2 => Box::new(day02::Day02),
+        3 => Box::new(day03::Day03),
+        4 => Box::new(day04::Day04),
+        5 => Box::new(day05::Day05),
+        6 => Box::new(day06::Day06),
+        7 => Box::new(day07::Day07),
+        8 => Box::new(day08::Day08::default()),
+        9 => Box::new(day09::Day09),
+        10 => Box::new(day10::Day10),
+        11 => Box::new(day11::Day11),
+        12 => Box::new(day12::Day12::default()),
+        13 => Box::new(day13::Day13),
+        14 => Box::new(day14::Day14),
+        15 => Box::new(day15::Day15),
+        _ => return None,
+    };
+    Some(solver)
+}
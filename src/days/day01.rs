@@ -0,0 +1,32 @@
+use crate::parser;
+use crate::solver::{Answer, Solver, SolverError};
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let modules = parser::parse_lines::<i32>(input)?;
+        Ok(modules.into_iter().map(fuel_needed).sum::<i32>().into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let modules = parser::parse_lines::<i32>(input)?;
+        Ok(modules
+            .into_iter()
+            .map(total_fuel_needed)
+            .sum::<i32>()
+            .into())
+    }
+}
+
+pub fn fuel_needed(weight: i32) -> i32 {
+    weight / 3 - 2
+}
+
+/// Fuel for the module and for the fuel itself.
+pub fn total_fuel_needed(weight: i32) -> i32 {
+    match fuel_needed(weight) {
+        x if x < 1 => 0,
+        x => x + total_fuel_needed(x),
+    }
+}
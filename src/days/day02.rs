@@ -0,0 +1,77 @@
+use crate::intcode::{self, Engine, FastMachine, IntCodeError, Machine, Stats};
+use crate::solver::{Answer, Solver, SolverError};
+
+/// Output the gravity assist program has to produce in part 2.
+pub const EXPECTED: i64 = 19_690_720;
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(run_with(&program, 12, 2)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        match find_noun_verb(&program, EXPECTED, None) {
+            Some((noun, verb)) => Ok((noun * 100 + verb).into()),
+            None => Err(SolverError::no_solution(format!(
+                "no noun and verb produce {}",
+                EXPECTED
+            ))),
+        }
+    }
+}
+
+/// Runs the program with `noun` and `verb` at addresses 1 and 2, and returns
+/// the value left at address 0.
+pub fn run_with(program: &[i64], noun: i64, verb: i64) -> Result<i64, IntCodeError> {
+    let mut machine = machine(program, noun, verb);
+    machine.run()?;
+    Ok(machine.memory()[0])
+}
+
+/// Tries every noun and verb until the program produces `expected`. Runs that
+/// fail count as not matching. If `stats` is given, the statistics of every
+/// run are added to it; otherwise the runs use the faster `FastMachine`.
+pub fn find_noun_verb(
+    program: &[i64],
+    expected: i64,
+    mut stats: Option<&mut Stats>,
+) -> Option<(i64, i64)> {
+    for i in 0..10000 {
+        let (noun, verb) = (i / 100, i % 100);
+
+        let result = match stats.as_mut() {
+            Some(total) => {
+                let mut machine = machine(program, noun, verb);
+                machine.set_stats(Stats::new());
+                let ok = machine.run().is_ok();
+                if let Some(run) = machine.stats() {
+                    total.merge(run);
+                }
+                (ok, machine.memory()[0])
+            }
+            None => {
+                let mut machine = FastMachine::new(program);
+                machine.set(1, noun);
+                machine.set(2, verb);
+                (machine.run().is_ok(), machine.get(0))
+            }
+        };
+
+        if result == (true, expected) {
+            return Some((noun, verb));
+        }
+    }
+
+    None
+}
+
+fn machine(program: &[i64], noun: i64, verb: i64) -> Machine {
+    let mut machine = Machine::new(program);
+    machine.memory_mut()[1] = noun;
+    machine.memory_mut()[2] = verb;
+    machine
+}
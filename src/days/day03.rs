@@ -0,0 +1,44 @@
+use crate::solver::{Answer, Solver, SolverError};
+use crate::wires::{self, Position};
+use std::collections::HashMap;
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    /// Manhattan distance to the closest intersection.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let coords = wires(input)?;
+        coords[0]
+            .iter()
+            .filter(|i| coords[1].contains(i))
+            .map(Position::dist)
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| SolverError::no_solution("the wires don't cross"))
+    }
+
+    /// Fewest combined steps to an intersection.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let coords = wires(input)?;
+        let first: HashMap<_, _> = coords[0].iter().rev().map(|i| (i, i.path())).collect();
+        let second: HashMap<_, _> = coords[1].iter().rev().map(|i| (i, i.path())).collect();
+
+        first
+            .iter()
+            .filter_map(|(p, a)| second.get(p).map(|b| a + b))
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| SolverError::no_solution("the wires don't cross"))
+    }
+}
+
+fn wires(input: &str) -> Result<Vec<Vec<Position>>, SolverError> {
+    let moves = wires::parse_wires(input)?;
+    if moves.len() != 2 {
+        return Err(SolverError::parse(format!(
+            "expected two wires, got {}",
+            moves.len()
+        )));
+    }
+    Ok(wires::get_wires(&moves))
+}
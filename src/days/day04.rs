@@ -0,0 +1,44 @@
+use crate::password::Password;
+use crate::solver::{Answer, Solver, SolverError};
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let (min, max) = range(input)?;
+        Ok(count(min, max, Password::is_valid).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let (min, max) = range(input)?;
+        Ok(count(min, max, Password::is_strictly_valid).into())
+    }
+}
+
+/// Counts the passwords in `min..=max` accepted by `valid`.
+pub fn count<F: Fn(&Password) -> bool>(min: i32, max: i32, valid: F) -> usize {
+    let mut counter = 0;
+    let mut current = Password::new(min);
+    while current.value() <= max {
+        if valid(&current) {
+            counter += 1;
+        }
+        match current.next() {
+            Some(p) => current = p,
+            _ => break,
+        }
+    }
+    counter
+}
+
+/// Parses a range written as `min-max`.
+fn range(input: &str) -> Result<(i32, i32), SolverError> {
+    let range: Vec<&str> = input.trim().split('-').map(str::trim).collect();
+    match range.as_slice() {
+        [min, max] => Ok((min.parse()?, max.parse()?)),
+        _ => Err(SolverError::parse(format!(
+            "expected a range like 123-456, got '{}'",
+            input.trim()
+        ))),
+    }
+}
@@ -0,0 +1,36 @@
+use crate::intcode::{self, Engine, Machine};
+use crate::solver::{Answer, Solver, SolverError};
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(run_diagnostic(&mut Machine::new(program), 1)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(run_diagnostic(&mut Machine::new(program), 5)?.into())
+    }
+}
+
+/// Runs a program that reads one system ID and reports its result as the
+/// last output. Any earlier non-zero output is a failed self-test.
+pub fn run_diagnostic(machine: &mut Machine, system_id: i64) -> Result<i64, SolverError> {
+    machine.push_input(system_id);
+    machine.run()?;
+
+    let output = machine.take_output();
+    match output.split_last() {
+        Some((&result, tests)) => match tests.iter().position(|&v| v != 0) {
+            Some(i) => Err(SolverError::no_solution(format!(
+                "test {} failed with {}",
+                i + 1,
+                tests[i]
+            ))),
+            None => Ok(result),
+        },
+        None => Err(SolverError::no_solution("the program gave no output")),
+    }
+}
@@ -0,0 +1,55 @@
+use crate::parser::{self, ParseError};
+use crate::solver::{Answer, Solver, SolverError};
+use std::collections::HashMap;
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    /// Total number of direct and indirect orbits.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let edges = orbits(input)?;
+        let count: usize = edges.keys().map(|k| path(k, &edges).len()).sum();
+        Ok(count.into())
+    }
+
+    /// Orbital transfers needed to move from YOU to the object SAN orbits.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let edges = orbits(input)?;
+        let you = path("YOU", &edges);
+        let san = path("SAN", &edges);
+
+        let first = you
+            .iter()
+            .find(|i| san.contains(i))
+            .ok_or_else(|| SolverError::no_solution("YOU and SAN have no common object"))?;
+
+        let count = you.iter().position(|s| s == first).unwrap()
+            + san.iter().position(|s| s == first).unwrap();
+        Ok(count.into())
+    }
+}
+
+/// Maps every object to the object it orbits.
+fn orbits(input: &str) -> Result<HashMap<&str, &str>, SolverError> {
+    let orbits = parser::parse_records(input, |l| {
+        let mut parts = l.split(')');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(center), Some(object), None) => Ok((object, center)),
+            _ => Err(ParseError::new(1, format!("invalid orbit '{}'", l))),
+        }
+    })?;
+    Ok(orbits.into_iter().collect())
+}
+
+/// Objects `key` orbits, nearest first.
+fn path<'a>(key: &'a str, map: &HashMap<&'a str, &'a str>) -> Vec<&'a str> {
+    let mut result = Vec::new();
+
+    let mut item = key;
+    while let Some(&next) = map.get(item) {
+        result.push(next);
+        item = next;
+    }
+
+    result
+}
@@ -0,0 +1,170 @@
+use crate::intcode::network::Network;
+use crate::intcode::{self, IntCodeError, Machine, Memory};
+use crate::solver::{Answer, Solver, SolverError};
+use permutohedron::LexicalPermutation;
+use std::thread;
+
+pub struct Day07;
+
+/// Permutations handed to a worker at a time.
+const BATCH_SIZE: usize = 256;
+
+impl Solver for Day07 {
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(max_signal_parallel(&program, &[0, 1, 2, 3, 4], threads())?
+            .1
+            .into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(max_signal_parallel(&program, &[5, 6, 7, 8, 9], threads())?
+            .1
+            .into())
+    }
+}
+
+fn threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Tries every order of `phases`, one amplifier per phase, and returns the
+/// one giving the highest thruster signal, with the signal. Of equally good
+/// orders the lexically smallest one wins.
+pub fn max_signal(program: &[i64], phases: &[i64]) -> Result<(Vec<i64>, i64), IntCodeError> {
+    let program = Memory::from(program.to_vec());
+    let mut phase_settings = phases.to_vec();
+    phase_settings.sort_unstable();
+
+    let mut best = None;
+    loop {
+        let signal = run(&program, &phase_settings)?;
+        best = better(best, (phase_settings.clone(), signal));
+        if !phase_settings.next_permutation() {
+            break;
+        }
+    }
+
+    best.ok_or_else(|| IntCodeError::new("No phase settings"))
+}
+
+/// Like `max_signal`, but spreads the orders over a pool of `threads`
+/// worker threads.
+pub fn max_signal_parallel(
+    program: &[i64],
+    phases: &[i64],
+    threads: usize,
+) -> Result<(Vec<i64>, i64), IntCodeError> {
+    let program = Memory::from(program.to_vec());
+    let (sender, receiver) = crossbeam_channel::bounded::<Vec<Vec<i64>>>(threads.max(1) * 2);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| {
+                let receiver = receiver.clone();
+                let program = &program;
+                scope.spawn(move || {
+                    let mut best = None;
+                    for batch in receiver {
+                        for phase_settings in batch {
+                            let signal = run(program, &phase_settings)?;
+                            best = better(best, (phase_settings, signal));
+                        }
+                    }
+                    Ok(best)
+                })
+            })
+            .collect();
+        drop(receiver);
+
+        // Orders are generated as they are needed; the bounded channel keeps
+        // memory flat even for many amplifiers.
+        let mut phase_settings = phases.to_vec();
+        phase_settings.sort_unstable();
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        loop {
+            batch.push(phase_settings.clone());
+            let more = phase_settings.next_permutation();
+            if batch.len() == BATCH_SIZE || !more {
+                // Sending only fails once every worker has failed.
+                if sender.send(batch).is_err() || !more {
+                    break;
+                }
+                batch = Vec::with_capacity(BATCH_SIZE);
+            }
+        }
+        drop(sender);
+
+        let mut best = None;
+        let mut error = None;
+        for worker in workers {
+            match worker.join() {
+                Ok(Ok(Some(result))) => best = better(best, result),
+                Ok(Ok(None)) => {}
+                Ok(Err(err)) => {
+                    error.get_or_insert(err);
+                }
+                Err(_) => {
+                    error.get_or_insert(IntCodeError::new("Worker thread panicked"));
+                }
+            }
+        }
+
+        match error {
+            Some(err) => Err(err),
+            None => best.ok_or_else(|| IntCodeError::new("No phase settings")),
+        }
+    })
+}
+
+/// The higher signal, or the smaller order if the signals are equal.
+fn better(best: Option<(Vec<i64>, i64)>, candidate: (Vec<i64>, i64)) -> Option<(Vec<i64>, i64)> {
+    match best {
+        Some(best) if (best.1, &candidate.0) >= (candidate.1, &best.0) => Some(best),
+        _ => Some(candidate),
+    }
+}
+
+/// Runs one amplifier per phase setting, each feeding the next and the last
+/// feeding back into the first, until they all halt. Returns the last signal
+/// sent by the last one. With phases 0 to 4 every amplifier halts after one
+/// output, so this also covers a plain chain.
+pub fn amplify(program: &[i64], phase_settings: &[i64]) -> Result<i64, IntCodeError> {
+    run(&Memory::from(program.to_vec()), phase_settings)
+}
+
+fn run(program: &Memory, phase_settings: &[i64]) -> Result<i64, IntCodeError> {
+    if phase_settings.is_empty() {
+        return Err(IntCodeError::new("No amplifiers"));
+    }
+    let mut amplifiers = amplifiers(program, phase_settings);
+    amplifiers.run()?;
+    amplifiers
+        .last_output(amplifiers.last())
+        .ok_or_else(|| IntCodeError::new("No output"))
+}
+
+/// Like `amplify`, with every amplifier on its own thread.
+pub fn amplify_threaded(program: &[i64], phase_settings: &[i64]) -> Result<i64, IntCodeError> {
+    if phase_settings.is_empty() {
+        return Err(IntCodeError::new("No amplifiers"));
+    }
+    let mut amplifiers = amplifiers(&Memory::from(program.to_vec()), phase_settings);
+    amplifiers.run_threaded()?;
+    amplifiers
+        .last_output(amplifiers.last())
+        .ok_or_else(|| IntCodeError::new("No output"))
+}
+
+/// The amplifiers as a ring, with their phase settings and the initial
+/// signal 0 queued.
+fn amplifiers(program: &Memory, phase_settings: &[i64]) -> Network {
+    let mut network = Network::ring(phase_settings.iter().map(|&phase| {
+        let mut machine = Machine::new(program.clone());
+        machine.push_input(phase);
+        machine
+    }));
+    network.push_input(network.first(), 0);
+    network
+}
@@ -0,0 +1,101 @@
+use crate::parser;
+use crate::solver::{Answer, Solver, SolverError};
+
+pub const BLACK: u32 = 0;
+pub const WHITE: u32 = 1;
+pub const TRANSPARENT: u32 = 2;
+
+/// Decodes Space Image Format pictures of the given size.
+pub struct Day08 {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Default for Day08 {
+    fn default() -> Self {
+        Day08 {
+            width: 25,
+            height: 6,
+        }
+    }
+}
+
+impl Solver for Day08 {
+    /// Number of 1s times number of 2s in the layer with the fewest 0s.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let digits = digits(input)?;
+        let layer = self
+            .layers(&digits)?
+            .into_iter()
+            .min_by_key(|layer| count_digit(layer, 0))
+            .ok_or_else(|| SolverError::parse("the image is empty"))?;
+
+        Ok((count_digit(layer, 1) * count_digit(layer, 2)).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let image = self.decode(&digits(input)?)?;
+        let rows: Vec<String> = image
+            .chunks(self.width)
+            .map(|row| row.iter().map(|&p| ascii_pixel(p)).collect())
+            .collect();
+        Ok(rows.join("\n").into())
+    }
+}
+
+impl Day08 {
+    /// Reads the size given as `<x> <y>`, 25 x 6 without one. Returns `None`
+    /// if it isn't a pair of numbers.
+    pub fn from_args(args: &[String]) -> Option<Day08> {
+        match (args.first(), args.get(1)) {
+            (Some(x), Some(y)) => match (x.parse(), y.parse()) {
+                (Ok(width), Ok(height)) => Some(Day08 { width, height }),
+                _ => None,
+            },
+            _ => Some(Day08::default()),
+        }
+    }
+
+    /// Stacks the layers into the final image, one pixel per position.
+    pub fn decode(&self, digits: &[u32]) -> Result<Vec<u32>, SolverError> {
+        let layers = self.layers(digits)?;
+        Ok((0..self.width * self.height)
+            .map(|i| {
+                layers
+                    .iter()
+                    .map(|layer| layer[i])
+                    .find(|&p| p != TRANSPARENT)
+                    .unwrap_or(TRANSPARENT)
+            })
+            .collect())
+    }
+
+    fn layers<'a>(&self, digits: &'a [u32]) -> Result<Vec<&'a [u32]>, SolverError> {
+        let size = self.width * self.height;
+        if size == 0 || !digits.len().is_multiple_of(size) {
+            return Err(SolverError::parse(format!(
+                "image size {} is not a multiple of {} ({} x {})",
+                digits.len(),
+                size,
+                self.width,
+                self.height
+            )));
+        }
+        Ok(digits.chunks(size).collect())
+    }
+}
+
+pub fn digits(input: &str) -> Result<Vec<u32>, SolverError> {
+    Ok(parser::parse_digits(input)?)
+}
+
+fn count_digit(a: &[u32], digit: u32) -> usize {
+    a.iter().filter(|&v| *v == digit).count()
+}
+
+fn ascii_pixel(v: u32) -> char {
+    match v {
+        WHITE => '*',
+        _ => ' ',
+    }
+}
@@ -0,0 +1,19 @@
+use super::day05::run_diagnostic;
+use crate::intcode::{self, Machine};
+use crate::solver::{Answer, Solver, SolverError};
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    /// BOOST keycode produced in test mode.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(run_diagnostic(&mut Machine::new(program), 1)?.into())
+    }
+
+    /// Coordinates of the distress signal, produced in sensor boost mode.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(run_diagnostic(&mut Machine::new(program), 2)?.into())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::parser;
+use crate::solver::{Answer, Solver, SolverError};
+use num::integer::Integer;
+use std::collections::{BTreeMap, HashSet};
+use std::f64::consts::PI;
+use std::str::FromStr;
+
+pub type Point = (i32, i32);
+
+pub struct Day10;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Tile {
+    Empty,
+    Asteroid,
+}
+
+impl FromStr for Tile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "." => Ok(Tile::Empty),
+            "#" => Ok(Tile::Asteroid),
+            _ => Err("not a map tile".to_string()),
+        }
+    }
+}
+
+impl Solver for Day10 {
+    /// Number of asteroids visible from the best monitoring station.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let asteroids = asteroids(input)?;
+        let (_, visible) = best_position(&asteroids)
+            .ok_or_else(|| SolverError::no_solution("there are no asteroids"))?;
+        Ok(visible.into())
+    }
+
+    /// X * 100 + Y of the 200th asteroid vaporized from the station.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let asteroids = asteroids(input)?;
+        let (station, _) = best_position(&asteroids)
+            .ok_or_else(|| SolverError::no_solution("there are no asteroids"))?;
+        let (x, y) = vaporize_order(&asteroids, station)
+            .get(199)
+            .cloned()
+            .ok_or_else(|| SolverError::no_solution("fewer than 200 asteroids"))?;
+        Ok((x * 100 + y).into())
+    }
+}
+
+pub fn asteroids(input: &str) -> Result<Vec<Point>, SolverError> {
+    let grid = parser::parse_grid::<Tile>(input)?;
+    Ok(grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &tile)| tile == Tile::Asteroid)
+                .map(move |(x, _)| (x as i32, y as i32))
+        })
+        .collect())
+}
+
+/// The asteroid that sees the most others, with the number it sees.
+pub fn best_position(asteroids: &[Point]) -> Option<(Point, usize)> {
+    asteroids
+        .iter()
+        .map(|&a| {
+            let visible = asteroids
+                .iter()
+                .filter(|&&b| b != a)
+                .map(|&b| direction(a, b))
+                .collect::<HashSet<_>>()
+                .len();
+            (a, visible)
+        })
+        .max_by_key(|&(_, visible)| visible)
+}
+
+/// Asteroids in the order a laser at `station` hits them, starting straight
+/// up and rotating clockwise. Only the nearest asteroid in each direction is
+/// hit per rotation.
+pub fn vaporize_order(asteroids: &[Point], station: Point) -> Vec<Point> {
+    let mut lines: BTreeMap<Point, Vec<Point>> = BTreeMap::new();
+    for &a in asteroids.iter().filter(|&&a| a != station) {
+        lines.entry(direction(station, a)).or_default().push(a);
+    }
+
+    let mut lines: Vec<(f64, Vec<Point>)> = lines
+        .into_iter()
+        .map(|(dir, mut targets)| {
+            targets.sort_by_key(|&(x, y)| (x - station.0).abs() + (y - station.1).abs());
+            (angle(dir), targets)
+        })
+        .collect();
+    lines.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let rotations = lines.iter().map(|(_, t)| t.len()).max().unwrap_or(0);
+    (0..rotations)
+        .flat_map(|r| lines.iter().filter_map(move |(_, t)| t.get(r).cloned()))
+        .collect()
+}
+
+fn direction(from: Point, to: Point) -> Point {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    match dx.gcd(&dy) {
+        0 => (dx.signum(), dy.signum()),
+        gcd => (dx / gcd, dy / gcd),
+    }
+}
+
+/// Clockwise angle from straight up, in [0, 2π). Y grows downwards.
+fn angle(dir: Point) -> f64 {
+    let a = (dir.0 as f64).atan2(-dir.1 as f64);
+    match a < 0.0 {
+        true => a + 2.0 * PI,
+        false => a,
+    }
+}
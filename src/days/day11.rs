@@ -0,0 +1,112 @@
+use crate::intcode::{self, Machine, StepResult};
+use crate::solver::{Answer, Solver, SolverError};
+use std::collections::HashMap;
+
+pub const BLACK: i64 = 0;
+pub const WHITE: i64 = 1;
+
+pub type Hull = HashMap<(i64, i64), i64>;
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    /// Number of panels painted at least once, starting on a black panel.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(paint(&mut Machine::new(program), BLACK)?.len().into())
+    }
+
+    /// The registration identifier painted when starting on a white panel.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        Ok(render(&paint(&mut Machine::new(program), WHITE)?).into())
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    fn turn(self, turn: i64) -> Self {
+        match (self, turn) {
+            (Direction::Up, 0) => Direction::Left,
+            (Direction::Up, _) => Direction::Right,
+            (Direction::Right, 0) => Direction::Up,
+            (Direction::Right, _) => Direction::Down,
+            (Direction::Down, 0) => Direction::Right,
+            (Direction::Down, _) => Direction::Left,
+            (Direction::Left, 0) => Direction::Down,
+            (Direction::Left, _) => Direction::Up,
+        }
+    }
+
+    fn step(self, (x, y): (i64, i64)) -> (i64, i64) {
+        match self {
+            Direction::Up => (x, y - 1),
+            Direction::Right => (x + 1, y),
+            Direction::Down => (x, y + 1),
+            Direction::Left => (x - 1, y),
+        }
+    }
+}
+
+/// Runs the painting robot on `machine` and returns the color of every panel
+/// it painted. The robot starts on a panel of `start_color`.
+pub fn paint(machine: &mut Machine, start_color: i64) -> Result<Hull, SolverError> {
+    let mut hull = Hull::new();
+    let mut pos = (0, 0);
+    let mut dir = Direction::Up;
+    let mut color = None;
+
+    if start_color != BLACK {
+        hull.insert(pos, start_color);
+    }
+
+    loop {
+        match machine.step()? {
+            StepResult::NeedInput => {
+                machine.push_input(*hull.get(&pos).unwrap_or(&BLACK));
+            }
+            StepResult::Output(val) => match color.take() {
+                None => color = Some(val),
+                Some(c) => {
+                    hull.insert(pos, c);
+                    dir = dir.turn(val);
+                    pos = dir.step(pos);
+                }
+            },
+            StepResult::Halted => return Ok(hull),
+            StepResult::Continue => {}
+        }
+    }
+}
+
+/// Smallest rectangle `(x_min, y_min, width, height)` holding every panel.
+pub fn bounds(hull: &Hull) -> (i64, i64, i64, i64) {
+    let x_min = hull.keys().map(|p| p.0).min().unwrap_or(0);
+    let x_max = hull.keys().map(|p| p.0).max().unwrap_or(0);
+    let y_min = hull.keys().map(|p| p.1).min().unwrap_or(0);
+    let y_max = hull.keys().map(|p| p.1).max().unwrap_or(0);
+
+    (x_min, y_min, x_max - x_min + 1, y_max - y_min + 1)
+}
+
+pub fn render(hull: &Hull) -> String {
+    let (x_min, y_min, width, height) = bounds(hull);
+    let rows: Vec<String> = (y_min..y_min + height)
+        .map(|y| {
+            (x_min..x_min + width)
+                .map(|x| match hull.get(&(x, y)) {
+                    Some(&WHITE) => '*',
+                    _ => ' ',
+                })
+                .collect()
+        })
+        .collect();
+    rows.join("\n")
+}
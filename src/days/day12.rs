@@ -0,0 +1,160 @@
+use crate::parser::{self, ParseError};
+use crate::solver::{Answer, Solver, SolverError};
+use num::integer::Integer;
+use std::ops::{Add, Sub};
+
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct Vec3D(pub i32, pub i32, pub i32);
+
+impl Add for Vec3D {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+impl Sub for Vec3D {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+    }
+}
+
+impl Vec3D {
+    pub fn signum(&self) -> Vec3D {
+        Vec3D(self.0.signum(), self.1.signum(), self.2.signum())
+    }
+
+    pub fn abs(&self) -> Vec3D {
+        Vec3D(self.0.abs(), self.1.abs(), self.2.abs())
+    }
+
+    fn axis(&self, axis: usize) -> i32 {
+        match axis {
+            0 => self.0,
+            1 => self.1,
+            _ => self.2,
+        }
+    }
+}
+
+/// A moon's position and velocity.
+pub type Moon = (Vec3D, Vec3D);
+
+/// Simulates the moons of Jupiter.
+pub struct Day12 {
+    /// Steps simulated before measuring the energy in part 1.
+    pub steps: usize,
+}
+
+impl Default for Day12 {
+    fn default() -> Self {
+        Day12 { steps: 1000 }
+    }
+}
+
+impl Solver for Day12 {
+    /// Total energy after `steps` steps.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let mut moons = moons(input)?;
+        for _ in 0..self.steps {
+            moons = step(&moons);
+        }
+        Ok(moons.iter().map(|&m| energy(m)).sum::<i32>().into())
+    }
+
+    /// Steps until the moons first return to a previous state.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let moons = moons(input)?;
+        let period = (0..3)
+            .map(|axis| {
+                moons
+                    .iter()
+                    .map(|&(m, v)| (m.axis(axis), v.axis(axis)))
+                    .collect::<Vec<(i32, i32)>>()
+            })
+            .map(|axis| find_repeat_for_axis(&axis))
+            .fold(1_i64, |total, steps| total.lcm(&steps));
+        Ok(period.into())
+    }
+}
+
+pub fn moons(input: &str) -> Result<Vec<Moon>, SolverError> {
+    Ok(parser::parse_records(input, |l| {
+        parse_moon(l).map(|m| (m, Vec3D(0, 0, 0)))
+    })?)
+}
+
+/// Applies gravity, then velocity.
+pub fn step(moons: &[Moon]) -> Vec<Moon> {
+    moons
+        .iter()
+        .map(|&(m1, v1)| {
+            let v = moons
+                .iter()
+                .fold(v1, |rv, &(m2, _)| rv + (m2 - m1).signum());
+            (m1 + v, v)
+        })
+        .collect()
+}
+
+pub fn energy(moon: Moon) -> i32 {
+    let (m, v) = (moon.0.abs(), moon.1.abs());
+    (m.0 + m.1 + m.2) * (v.0 + v.1 + v.2)
+}
+
+/// The axes move independently, and each step can be undone, so the first
+/// repeated state of an axis is its starting state.
+fn find_repeat_for_axis(start: &[(i32, i32)]) -> i64 {
+    let mut moons = start.to_vec();
+    let mut steps = 0;
+
+    loop {
+        moons = moons
+            .iter()
+            .map(|&(m1, v1)| {
+                let v = moons
+                    .iter()
+                    .fold(v1, |rv, &(m2, _)| rv + (m2 - m1).signum());
+                (m1 + v, v)
+            })
+            .collect();
+        steps += 1;
+
+        if moons == start {
+            return steps;
+        }
+    }
+}
+
+/// Parses a position like `<x=-1, y=0, z=2>`.
+fn parse_moon(line: &str) -> Result<Vec3D, ParseError> {
+    let invalid = |column| ParseError::new(column, format!("invalid moon '{}'", line));
+    let inner = line
+        .strip_prefix('<')
+        .and_then(|l| l.strip_suffix('>'))
+        .ok_or_else(|| invalid(1))?;
+
+    // Columns start at 2, after the '<'.
+    let mut values = Vec::new();
+    let mut column = 2;
+    for field in inner.split(',') {
+        let value = match field.find('=') {
+            Some(i) => &field[i + 1..],
+            None => return Err(invalid(column)),
+        };
+        let start = column + field.len() - value.len();
+        let value = value.trim();
+        values.push(value.parse::<i32>().map_err(|err| {
+            ParseError::new(start, format!("invalid coordinate '{}': {}", value, err))
+        })?);
+        column += field.len() + 1;
+    }
+
+    match values.as_slice() {
+        &[x, y, z] => Ok(Vec3D(x, y, z)),
+        _ => Err(invalid(1)),
+    }
+}
@@ -0,0 +1,152 @@
+use crate::intcode::{self, Machine, StepResult};
+use crate::solver::{Answer, Solver, SolverError};
+use std::collections::HashMap;
+
+pub const EMPTY: i64 = 0;
+pub const WALL: i64 = 1;
+pub const BLOCK: i64 = 2;
+pub const PADDLE: i64 = 3;
+pub const BALL: i64 = 4;
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    /// Number of block tiles on the screen when the game exits.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let program = intcode::parse_program(input)?;
+        let mut arcade = Arcade::new();
+        arcade.play(&mut Machine::new(program), |_| {})?;
+        Ok(arcade.count_blocks().into())
+    }
+
+    /// Score after breaking every block, with the paddle following the ball.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let mut program = intcode::parse_program(input)?;
+        if program.is_empty() {
+            return Err(SolverError::parse("the program is empty"));
+        }
+        // Two quarters in address 0 play the game for free.
+        program[0] = 2;
+
+        let mut arcade = Arcade::new();
+        arcade.play(&mut Machine::new(program), |_| {})?;
+        Ok(arcade.score.into())
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum OutputType {
+    X,
+    Y,
+    Tile,
+}
+
+/// The screen and joystick of the arcade cabinet.
+pub struct Arcade {
+    x: i64,
+    y: i64,
+    pub score: i64,
+    ball_x: i64,
+    paddle_x: i64,
+    next_output: OutputType,
+    pub map: HashMap<(i64, i64), i64>,
+}
+
+impl Default for Arcade {
+    fn default() -> Self {
+        Arcade::new()
+    }
+}
+
+impl Arcade {
+    pub fn new() -> Self {
+        Arcade {
+            x: 0,
+            y: 0,
+            score: 0,
+            ball_x: 0,
+            paddle_x: 0,
+            next_output: OutputType::X,
+            map: HashMap::new(),
+        }
+    }
+
+    /// Runs the game until it halts. `on_input` is called with the screen
+    /// every time the game waits for the joystick.
+    pub fn play<F: FnMut(&Arcade)>(
+        &mut self,
+        machine: &mut Machine,
+        mut on_input: F,
+    ) -> Result<(), SolverError> {
+        loop {
+            match machine.step()? {
+                StepResult::NeedInput => {
+                    on_input(self);
+                    machine.push_input(self.input());
+                }
+                StepResult::Output(v) => self.output(v),
+                StepResult::Halted => return Ok(()),
+                StepResult::Continue => {}
+            }
+        }
+    }
+
+    /// Moves the paddle towards the ball.
+    pub fn input(&self) -> i64 {
+        (self.ball_x - self.paddle_x).signum()
+    }
+
+    pub fn output(&mut self, val: i64) {
+        match self.next_output {
+            OutputType::X => {
+                self.x = val;
+                self.next_output = OutputType::Y;
+            }
+            OutputType::Y => {
+                self.y = val;
+                self.next_output = OutputType::Tile;
+            }
+            OutputType::Tile => {
+                match (self.x, val) {
+                    (-1, _) => self.score = val,
+                    (x, BALL) => {
+                        self.ball_x = x;
+                        self.map.insert((x, self.y), val);
+                    }
+                    (x, PADDLE) => {
+                        self.paddle_x = x;
+                        self.map.insert((x, self.y), val);
+                    }
+                    (x, _) => {
+                        self.map.insert((x, self.y), val);
+                    }
+                }
+                self.next_output = OutputType::X;
+            }
+        }
+    }
+
+    pub fn count_blocks(&self) -> usize {
+        self.map.values().filter(|&&t| t == BLOCK).count()
+    }
+
+    pub fn render(&self) -> String {
+        let x_max = self.map.keys().map(|p| p.0).max().unwrap_or(0);
+        let y_max = self.map.keys().map(|p| p.1).max().unwrap_or(0);
+
+        let rows: Vec<String> = (0..=y_max)
+            .map(|y| {
+                (0..=x_max)
+                    .map(|x| match self.map.get(&(x, y)) {
+                        Some(&WALL) => '#',
+                        Some(&BLOCK) => '*',
+                        Some(&PADDLE) => '-',
+                        Some(&BALL) => 'o',
+                        _ => ' ',
+                    })
+                    .collect()
+            })
+            .collect();
+        rows.join("\n")
+    }
+}
@@ -0,0 +1,70 @@
+use crate::nanofactory::{NanoFactory, Reaction, Term};
+use crate::parser::{self, ParseError};
+use crate::solver::{Answer, Solver, SolverError};
+use std::collections::HashSet;
+
+/// Ore available in part 2.
+pub const ORE: i64 = 1_000_000_000_000;
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    /// Ore needed to produce one fuel.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let factory = factory(input)?;
+        Ok(ore_for_fuel(&factory, 1)?.into())
+    }
+
+    /// Most fuel that can be produced from `ORE` ore.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let factory = factory(input)?;
+        Ok(max_fuel(&factory, ORE)?.into())
+    }
+}
+
+pub fn factory(input: &str) -> Result<NanoFactory, SolverError> {
+    let reactions = parser::parse_records(input, |l| {
+        Reaction::parse(l).map_err(|err| ParseError::new(1, err))
+    })?;
+
+    Ok(NanoFactory {
+        reactions: reactions.into_iter().collect(),
+    })
+}
+
+pub fn ore_for_fuel(factory: &NanoFactory, fuel: i64) -> Result<i64, SolverError> {
+    let target = Term {
+        chemical: String::from("FUEL"),
+        amount: 1,
+    };
+    let (ore, _) = factory
+        .produce(fuel, &target, HashSet::new())
+        .map_err(|_| SolverError::parse("no reaction produces FUEL"))?;
+    Ok(ore)
+}
+
+/// Binary search for the most fuel `ore` ore is enough for.
+pub fn max_fuel(factory: &NanoFactory, ore: i64) -> Result<i64, SolverError> {
+    let per_fuel = ore_for_fuel(factory, 1)?;
+    if per_fuel <= 0 {
+        return Err(SolverError::no_solution("fuel needs no ore"));
+    }
+
+    // Leftovers make later fuel cheaper, so this much is always possible.
+    let mut low = ore / per_fuel;
+    let mut high = low.max(1) * 2;
+    while ore_for_fuel(factory, high)? <= ore {
+        low = high;
+        high *= 2;
+    }
+
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        match ore_for_fuel(factory, mid)? <= ore {
+            true => low = mid,
+            false => high = mid,
+        }
+    }
+
+    Ok(low)
+}
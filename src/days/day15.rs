@@ -0,0 +1,149 @@
+use crate::intcode::{self, Engine, Machine, Stats, Tracer};
+use crate::solver::{Answer, Solver, SolverError};
+use pathfinding::prelude::{bfs, dijkstra_all};
+use std::collections::{HashMap, VecDeque};
+
+pub type Pos = (i64, i64);
+
+pub const WALL: i64 = 0;
+pub const OPEN: i64 = 1;
+pub const OXYGEN: i64 = 2;
+pub const START: i64 = 3;
+
+const MOVES: [(i64, Pos); 4] = [(1, (0, -1)), (2, (0, 1)), (3, (-1, 0)), (4, (1, 0))];
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    /// Fewest moves from the start to the oxygen system.
+    fn part1(&self, input: &str) -> Result<Answer, SolverError> {
+        let map = explore(&mut Machine::new(intcode::parse_program(input)?))?;
+        Ok(distance_to_oxygen(&map)?.into())
+    }
+
+    /// Minutes until oxygen has spread to every open tile.
+    fn part2(&self, input: &str) -> Result<Answer, SolverError> {
+        let map = explore(&mut Machine::new(intcode::parse_program(input)?))?;
+        Ok(fill_time(&map)?.into())
+    }
+}
+
+pub fn distance_to_oxygen(map: &HashMap<Pos, i64>) -> Result<usize, SolverError> {
+    let goal = oxygen_system(map)?;
+    let path = bfs(&(0, 0), |p| successors(map, p), |p| *p == goal)
+        .ok_or_else(|| SolverError::no_solution("the oxygen system can't be reached"))?;
+    Ok(path.len() - 1)
+}
+
+pub fn fill_time(map: &HashMap<Pos, i64>) -> Result<i64, SolverError> {
+    let goal = oxygen_system(map)?;
+    let minutes = dijkstra_all(&goal, |p| {
+        successors(map, p)
+            .into_iter()
+            .map(|p| (p, 1))
+            .collect::<Vec<_>>()
+    })
+    .values()
+    .map(|&(_, dist)| dist)
+    .max()
+    .unwrap_or(0);
+    Ok(minutes)
+}
+
+/// Maps the maze breadth first. Every open tile keeps the VM of a droid
+/// standing on it, and each neighbour is probed by a clone of that VM, so the
+/// droid never has to walk back.
+///
+/// Clones start without a tracer or statistics, so those of `machine` are
+/// lent to each probe in turn and handed back at the end, covering every
+/// instruction run.
+pub fn explore(machine: &mut Machine) -> Result<HashMap<Pos, i64>, SolverError> {
+    let mut tracer = machine.take_tracer();
+    let mut stats = machine.take_stats();
+    let map = map_maze(machine, &mut tracer, &mut stats);
+    if let Some(tracer) = tracer {
+        machine.set_tracer(tracer);
+    }
+    if let Some(stats) = stats {
+        machine.set_stats(stats);
+    }
+    map
+}
+
+fn map_maze(
+    start: &Machine,
+    tracer: &mut Option<Box<dyn Tracer + Send>>,
+    stats: &mut Option<Stats>,
+) -> Result<HashMap<Pos, i64>, SolverError> {
+    let mut map = HashMap::new();
+    map.insert((0, 0), START);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(((0, 0), start.clone()));
+
+    while let Some((pos, machine)) = queue.pop_front() {
+        for &(dir, (dx, dy)) in MOVES.iter() {
+            let next = (pos.0 + dx, pos.1 + dy);
+            if map.contains_key(&next) {
+                continue;
+            }
+
+            let mut droid = machine.clone();
+            if let Some(tracer) = tracer.take() {
+                droid.set_tracer(tracer);
+            }
+            if let Some(stats) = stats.take() {
+                droid.set_stats(stats);
+            }
+            droid.push_input(dir);
+            let status = droid.run_until_output();
+            *tracer = droid.take_tracer();
+            *stats = droid.take_stats();
+            let status = status?.ok_or_else(|| SolverError::no_solution("the droid halted"))?;
+
+            map.insert(next, status);
+            if status != WALL {
+                queue.push_back((next, droid));
+            }
+        }
+    }
+
+    Ok(map)
+}
+
+pub fn render(map: &HashMap<Pos, i64>) -> String {
+    let x_min = map.keys().map(|p| p.0).min().unwrap_or(0);
+    let x_max = map.keys().map(|p| p.0).max().unwrap_or(0);
+    let y_min = map.keys().map(|p| p.1).min().unwrap_or(0);
+    let y_max = map.keys().map(|p| p.1).max().unwrap_or(0);
+
+    let rows: Vec<String> = (y_min..=y_max)
+        .map(|y| {
+            (x_min..=x_max)
+                .map(|x| match map.get(&(x, y)) {
+                    Some(&WALL) => '#',
+                    Some(&OPEN) => '.',
+                    Some(&OXYGEN) => '*',
+                    Some(&START) => 'X',
+                    _ => ' ',
+                })
+                .collect()
+        })
+        .collect();
+    rows.join("\n")
+}
+
+fn oxygen_system(map: &HashMap<Pos, i64>) -> Result<Pos, SolverError> {
+    map.iter()
+        .find(|&(_, v)| *v == OXYGEN)
+        .map(|(pos, _)| *pos)
+        .ok_or_else(|| SolverError::no_solution("the oxygen system wasn't found"))
+}
+
+fn successors(map: &HashMap<Pos, i64>, pos: &Pos) -> Vec<Pos> {
+    MOVES
+        .iter()
+        .map(|&(_, (dx, dy))| (pos.0 + dx, pos.1 + dy))
+        .filter(|p| map.get(p).is_some_and(|&v| v != WALL))
+        .collect()
+}
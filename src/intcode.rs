@@ -3,6 +3,35 @@ use std::error::Error;
 use std::fmt;
 use std::io;
 
+mod adapters;
+pub mod ascii;
+pub mod asm;
+mod bignum;
+pub mod compile;
+pub mod disasm;
+mod engine;
+mod fast;
+mod machine;
+mod memory;
+pub mod network;
+mod program;
+pub mod server;
+mod stats;
+mod trace;
+
+pub use self::adapters::{iter_input, Console, Input, IterInput, Output};
+pub use self::bignum::BigMachine;
+pub use self::engine::Engine;
+pub use self::fast::FastMachine;
+pub use self::machine::{Machine, StepResult};
+pub use self::memory::Memory;
+pub use self::program::{
+    decode_binary, decode_program, encode_program, load_program, parse_program, program_from_args,
+    program_from_args_or_exit, read_program, LoadError, BINARY_FLAG, BINARY_MAGIC,
+};
+pub use self::stats::{Stats, STATS_ENV};
+pub use self::trace::{FileTracer, TraceEvent, Tracer, TRACE_ENV};
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ParameterMode {
     Position = 0,
@@ -36,12 +65,54 @@ impl TryFrom<i64> for ParameterMode {
     }
 }
 
+/// What the VM does when an addition, multiplication or relative base
+/// adjustment overflows an `i64`.
+///
+/// Exact results whatever the size aren't a policy here: they need memory
+/// that holds bigger words than `i64`, which every other engine is built
+/// around. For those, run the program on a `BigMachine` instead.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Arithmetic {
+    /// Fail with `IntCodeError::Overflow`.
+    #[default]
+    Checked,
+    /// Wrap around, two's complement.
+    Wrapping,
+}
+
+impl Arithmetic {
+    pub fn add(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Arithmetic::Checked => a.checked_add(b),
+            Arithmetic::Wrapping => Some(a.wrapping_add(b)),
+        }
+    }
+
+    pub fn mul(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Arithmetic::Checked => a.checked_mul(b),
+            Arithmetic::Wrapping => Some(a.wrapping_mul(b)),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Parameter {
     pub value: i64,
     pub mode: ParameterMode,
 }
 
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.mode, self.value) {
+            (ParameterMode::Position, v) => write!(f, "[{}]", v),
+            (ParameterMode::Immediate, v) => write!(f, "#{}", v),
+            (ParameterMode::Relative, v) if v < 0 => write!(f, "rb{}", v),
+            (ParameterMode::Relative, v) => write!(f, "rb+{}", v),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum Instruction {
     Sum {
@@ -84,9 +155,127 @@ pub enum Instruction {
     Halt,
 }
 
+impl Instruction {
+    /// Number of words the instruction occupies, including the opcode.
+    pub fn size(&self) -> usize {
+        match self {
+            Instruction::Sum { .. }
+            | Instruction::Prod { .. }
+            | Instruction::LessThan { .. }
+            | Instruction::Equals { .. } => 4,
+            Instruction::JumpIfTrue { .. } | Instruction::JumpIfFalse { .. } => 3,
+            Instruction::Input { .. }
+            | Instruction::Output { .. }
+            | Instruction::RelativeBaseOffset { .. } => 2,
+            Instruction::Halt => 1,
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::Sum { .. } => "ADD",
+            Instruction::Prod { .. } => "MUL",
+            Instruction::Input { .. } => "IN",
+            Instruction::Output { .. } => "OUT",
+            Instruction::JumpIfTrue { .. } => "JT",
+            Instruction::JumpIfFalse { .. } => "JF",
+            Instruction::LessThan { .. } => "LT",
+            Instruction::Equals { .. } => "EQ",
+            Instruction::RelativeBaseOffset { .. } => "ARB",
+            Instruction::Halt => "HLT",
+        }
+    }
+
+    /// The parameters in the order they appear in memory.
+    pub fn params(&self) -> Vec<Parameter> {
+        match *self {
+            Instruction::Sum { i1, i2, out }
+            | Instruction::Prod { i1, i2, out }
+            | Instruction::LessThan { i1, i2, out }
+            | Instruction::Equals { i1, i2, out } => vec![i1, i2, out],
+            Instruction::JumpIfTrue { i1, pc } | Instruction::JumpIfFalse { i1, pc } => {
+                vec![i1, pc]
+            }
+            Instruction::Input { out: i1 }
+            | Instruction::Output { i1 }
+            | Instruction::RelativeBaseOffset { i1 } => vec![i1],
+            Instruction::Halt => Vec::new(),
+        }
+    }
+
+    /// The parameters the instruction reads, i.e. all but the written one.
+    pub fn read_params(&self) -> Vec<Parameter> {
+        let mut params = self.params();
+        if self.write_param().is_some() {
+            params.pop();
+        }
+        params
+    }
+
+    /// The parameter the instruction writes to, if any.
+    pub fn write_param(&self) -> Option<Parameter> {
+        match *self {
+            Instruction::Sum { out, .. }
+            | Instruction::Prod { out, .. }
+            | Instruction::Input { out }
+            | Instruction::LessThan { out, .. }
+            | Instruction::Equals { out, .. } => Some(out),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let params: Vec<String> = self.params().iter().map(Parameter::to_string).collect();
+        match params.is_empty() {
+            true => write!(f, "{}", self.mnemonic()),
+            false => write!(f, "{:<5}{}", self.mnemonic(), params.join(", ")),
+        }
+    }
+}
+
 #[derive(Debug)]
-pub struct IntCodeError {
-    details: String,
+pub enum IntCodeError {
+    InvalidOpcode {
+        pc: usize,
+        op: i64,
+    },
+    InvalidMode {
+        pc: usize,
+        op: i64,
+        mode: i64,
+    },
+    AddressOutOfRange {
+        pc: usize,
+        instruction: Instruction,
+        address: i64,
+    },
+    ImmediateWrite {
+        pc: usize,
+        instruction: Instruction,
+    },
+    /// A result doesn't fit in an `i64` under `Arithmetic::Checked`.
+    Overflow {
+        pc: usize,
+        instruction: Instruction,
+    },
+    InputExhausted {
+        pc: usize,
+    },
+    Input {
+        pc: usize,
+        details: String,
+    },
+    Output {
+        pc: usize,
+        details: String,
+    },
+    Halted {
+        pc: usize,
+    },
+    Io(io::Error),
+    Other(String),
 }
 
 impl IntCodeError {
@@ -94,234 +283,262 @@ impl IntCodeError {
     where
         T: AsRef<str> + 'a,
     {
-        IntCodeError {
-            details: msg.as_ref().to_string(),
+        IntCodeError::Other(msg.as_ref().to_string())
+    }
+
+    /// The program counter of the instruction that failed, if known.
+    pub fn pc(&self) -> Option<usize> {
+        match *self {
+            IntCodeError::InvalidOpcode { pc, .. }
+            | IntCodeError::InvalidMode { pc, .. }
+            | IntCodeError::AddressOutOfRange { pc, .. }
+            | IntCodeError::ImmediateWrite { pc, .. }
+            | IntCodeError::Overflow { pc, .. }
+            | IntCodeError::InputExhausted { pc }
+            | IntCodeError::Input { pc, .. }
+            | IntCodeError::Output { pc, .. }
+            | IntCodeError::Halted { pc } => Some(pc),
+            IntCodeError::Io(_) | IntCodeError::Other(_) => None,
+        }
+    }
+
+    fn from_input(pc: usize, err: Box<dyn Error>) -> Self {
+        match err.downcast::<IntCodeError>() {
+            Ok(err) => match *err {
+                IntCodeError::InputExhausted { .. } => IntCodeError::InputExhausted { pc },
+                err => err,
+            },
+            Err(err) => IntCodeError::Input {
+                pc,
+                details: err.to_string(),
+            },
         }
     }
 }
 
 impl fmt::Display for IntCodeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.details)
+        match self {
+            IntCodeError::InvalidOpcode { pc, op } => {
+                write!(f, "Invalid instruction at {}: {}", pc, op)
+            }
+            IntCodeError::InvalidMode { pc, op, mode } => {
+                write!(f, "Invalid parameter mode {} at {}: {}", mode, pc, op)
+            }
+            IntCodeError::AddressOutOfRange {
+                pc,
+                instruction,
+                address,
+            } => write!(
+                f,
+                "Address {} out of range at {}: {:?}",
+                address, pc, instruction
+            ),
+            IntCodeError::ImmediateWrite { pc, instruction } => write!(
+                f,
+                "Write to immediate parameter at {}: {:?}",
+                pc, instruction
+            ),
+            IntCodeError::Overflow { pc, instruction } => {
+                write!(f, "Arithmetic overflow at {}: {:?}", pc, instruction)
+            }
+            IntCodeError::InputExhausted { pc } => write!(f, "No input at {}", pc),
+            IntCodeError::Input { pc, details } => {
+                write!(f, "Input failed at {}: {}", pc, details)
+            }
+            IntCodeError::Output { pc, details } => {
+                write!(f, "Output failed at {}: {}", pc, details)
+            }
+            IntCodeError::Halted { pc } => write!(f, "Machine halted at {}", pc),
+            IntCodeError::Io(err) => write!(f, "{}", err),
+            IntCodeError::Other(details) => write!(f, "{}", details),
+        }
     }
 }
 
 impl Error for IntCodeError {
-    fn description(&self) -> &str {
-        &self.details
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IntCodeError::Io(err) => Some(err),
+            _ => None,
+        }
     }
 }
 
 impl From<String> for IntCodeError {
     fn from(err: String) -> Self {
-        IntCodeError::new(err)
+        IntCodeError::Other(err)
     }
 }
 
 impl From<io::Error> for IntCodeError {
     fn from(err: io::Error) -> Self {
-        IntCodeError::new(err.to_string())
+        IntCodeError::Io(err)
     }
 }
 
+/// Input callback for programs that never read input. The pc of the failing
+/// instruction is filled in by `process_instruction`.
 pub fn err_input() -> Result<i64, Box<dyn Error>> {
-    Err(Box::new(IntCodeError::from(format!("No input"))))
+    Err(Box::new(IntCodeError::InputExhausted { pc: 0 }))
 }
 
-pub fn stdout_output(out: i64) -> () {
+pub fn stdout_output(out: i64) {
     println!("OUTPUT: {}", out);
-} 
+}
 
-pub fn process_instruction<FIn, FOut>(
-    program: &mut Vec<i64>,
-    pc: &mut usize,
-    relative_base: &mut i64,
-    input_fn: &mut FIn,
-    output_fn: &mut FOut,
-) -> Result<bool, Box<dyn Error>>
+/// Decodes the instruction at `pc` without executing it.
+pub fn decode(program: &Memory, pc: usize) -> Result<Instruction, IntCodeError> {
+    decode_with(|address| program[address], pc)
+}
+
+/// Like `decode`, for memory that isn't a `Memory`.
+pub(crate) fn decode_with<F>(word: F, pc: usize) -> Result<Instruction, IntCodeError>
 where
-    FIn: FnMut() -> Result<i64, Box<dyn Error>>,
-    FOut: FnMut(i64) -> ()
+    F: Fn(usize) -> i64,
 {
-    let op = program[*pc];
-    let opcode = op % 100;
-    let mode1 = ParameterMode::try_from((op / 100) % 10)?;
-    let mode2 = ParameterMode::try_from((op / 1000) % 10)?;
-    let mode3 = ParameterMode::try_from((op / 10000) % 10)?;
-    let instruction = match (opcode, mode1, mode2, mode3) {
-        (1, _, _, m) if m != ParameterMode::Immediate => Instruction::Sum {
-            i1: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
-            i2: Parameter {
-                value: program[*pc + 2],
-                mode: mode2,
-            },
-            out: Parameter {
-                value: program[*pc + 3],
-                mode: mode3,
-            },
+    let op = word(pc);
+    let param = |n: usize| -> Result<Parameter, IntCodeError> {
+        let mode = (op / 10_i64.pow(n as u32 + 1)) % 10;
+        Ok(Parameter {
+            value: word(pc + n),
+            mode: ParameterMode::try_from(mode).map_err(|_| IntCodeError::InvalidMode {
+                pc,
+                op,
+                mode,
+            })?,
+        })
+    };
+
+    let instruction = match op % 100 {
+        1 => Instruction::Sum {
+            i1: param(1)?,
+            i2: param(2)?,
+            out: param(3)?,
         },
-        (2, _, _, m) if m != ParameterMode::Immediate => Instruction::Prod {
-            i1: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
-            i2: Parameter {
-                value: program[*pc + 2],
-                mode: mode2,
-            },
-            out: Parameter {
-                value: program[*pc + 3],
-                mode: mode3,
-            },
+        2 => Instruction::Prod {
+            i1: param(1)?,
+            i2: param(2)?,
+            out: param(3)?,
         },
-        (3, m, _, _) if m != ParameterMode::Immediate => Instruction::Input {
-            out: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
+        3 => Instruction::Input { out: param(1)? },
+        4 => Instruction::Output { i1: param(1)? },
+        5 => Instruction::JumpIfTrue {
+            i1: param(1)?,
+            pc: param(2)?,
         },
-        (4, _, _, _) => Instruction::Output {
-            i1: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
+        6 => Instruction::JumpIfFalse {
+            i1: param(1)?,
+            pc: param(2)?,
         },
-        (5, _, _, _) => Instruction::JumpIfTrue {
-            i1: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
-            pc: Parameter {
-                value: program[*pc + 2],
-                mode: mode2,
-            },
+        7 => Instruction::LessThan {
+            i1: param(1)?,
+            i2: param(2)?,
+            out: param(3)?,
         },
-        (6, _, _, _) => Instruction::JumpIfFalse {
-            i1: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
-            pc: Parameter {
-                value: program[*pc + 2],
-                mode: mode2,
-            },
+        8 => Instruction::Equals {
+            i1: param(1)?,
+            i2: param(2)?,
+            out: param(3)?,
         },
-        (7, _, _, m) if m != ParameterMode::Immediate => Instruction::LessThan {
-            i1: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
-            i2: Parameter {
-                value: program[*pc + 2],
-                mode: mode2,
-            },
-            out: Parameter {
-                value: program[*pc + 3],
-                mode: mode3,
-            },
-        },
-        (8, _, _, m) if m != ParameterMode::Immediate => Instruction::Equals {
-            i1: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
-            i2: Parameter {
-                value: program[*pc + 2],
-                mode: mode2,
-            },
-            out: Parameter {
-                value: program[*pc + 3],
-                mode: mode3,
-            },
-        },
-        (9, _, _, _) => Instruction::RelativeBaseOffset {
-            i1: Parameter {
-                value: program[*pc + 1],
-                mode: mode1,
-            },
-        },
-        (99, _, _, _) => Instruction::Halt,
-        _ => {
-            return Err(Box::new(IntCodeError::new(format!(
-                "Invalid instruction at {}: {}",
-                *pc, program[*pc]
-            ))))
-        }
+        9 => Instruction::RelativeBaseOffset { i1: param(1)? },
+        99 => Instruction::Halt,
+        _ => return Err(IntCodeError::InvalidOpcode { pc, op }),
     };
 
-    //println!("{:?}", instruction);
+    match instruction.write_param() {
+        Some(out) if out.mode == ParameterMode::Immediate => {
+            Err(IntCodeError::ImmediateWrite { pc, instruction })
+        }
+        _ => Ok(instruction),
+    }
+}
+
+pub fn process_instruction<FIn, FOut>(
+    program: &mut Memory,
+    pc: &mut usize,
+    relative_base: &mut i64,
+    arithmetic: Arithmetic,
+    input_fn: &mut FIn,
+    output_fn: &mut FOut,
+) -> Result<bool, IntCodeError>
+where
+    FIn: FnMut() -> Result<i64, Box<dyn Error>>,
+    FOut: FnMut(i64),
+{
+    let instruction = decode(program, *pc)?;
+    let operands = Operands {
+        pc: *pc,
+        instruction,
+        relative_base: *relative_base,
+    };
 
     match instruction {
         Instruction::Sum { i1, i2, out } => {
-            let mem = try_get_address(&program, &relative_base, out)?;
-            program[mem] =
-                try_get_param(&program, &relative_base, i1)? + try_get_param(&program, &relative_base, i2)?;
+            let mem = operands.address(out)?;
+            let (a, b) = (operands.read(program, i1)?, operands.read(program, i2)?);
+            program[mem] = operands.overflow(arithmetic.add(a, b))?;
             *pc += 4;
             Ok(true)
         }
         Instruction::Prod { i1, i2, out } => {
-            let mem = try_get_address(&program, &relative_base, out)?;
-            program[mem] =
-                try_get_param(&program, &relative_base, i1)? * try_get_param(&program, &relative_base, i2)?;
+            let mem = operands.address(out)?;
+            let (a, b) = (operands.read(program, i1)?, operands.read(program, i2)?);
+            program[mem] = operands.overflow(arithmetic.mul(a, b))?;
             *pc += 4;
             Ok(true)
         }
         Instruction::Input { out } => {
-            let mem = try_get_address(&program, &relative_base, out)?;
-            program[mem] = input_fn()?;
+            let mem = operands.address(out)?;
+            program[mem] = input_fn().map_err(|err| IntCodeError::from_input(*pc, err))?;
             *pc += 2;
             Ok(true)
         }
         Instruction::Output { i1 } => {
-            output_fn(try_get_param(&program, &relative_base, i1)?);
+            output_fn(operands.read(program, i1)?);
             *pc += 2;
             Ok(true)
         }
         Instruction::JumpIfTrue { i1, pc: pc_new } => {
-            match try_get_param(&program, &relative_base, i1)? {
+            match operands.read(program, i1)? {
                 0 => *pc += 3,
-                _ => *pc = try_get_param(&program, &relative_base, pc_new)? as usize,
+                _ => *pc = operands.jump_target(program, pc_new)?,
             }
 
             Ok(true)
         }
         Instruction::JumpIfFalse { i1, pc: pc_new } => {
-            match try_get_param(&program, &relative_base, i1)? {
-                0 => *pc = try_get_param(&program, &relative_base, pc_new)? as usize,
+            match operands.read(program, i1)? {
+                0 => *pc = operands.jump_target(program, pc_new)?,
                 _ => *pc += 3,
             }
 
             Ok(true)
         }
         Instruction::LessThan { i1, i2, out } => {
-            let mem = try_get_address(&program, &relative_base, out)?;
-            program[mem] =
-                match try_get_param(&program, &relative_base, i1)? < try_get_param(&program, &relative_base, i2)? {
-                    true => 1,
-                    false => 0,
-                };
+            let mem = operands.address(out)?;
+            program[mem] = match operands.read(program, i1)? < operands.read(program, i2)? {
+                true => 1,
+                false => 0,
+            };
 
             *pc += 4;
             Ok(true)
         }
         Instruction::Equals { i1, i2, out } => {
-            let mem = try_get_address(&program, &relative_base, out)?;
-            program[mem] =
-                match try_get_param(&program, &relative_base, i1)? == try_get_param(&program, &relative_base, i2)? {
-                    true => 1,
-                    false => 0,
-                };
+            let mem = operands.address(out)?;
+            program[mem] = match operands.read(program, i1)? == operands.read(program, i2)? {
+                true => 1,
+                false => 0,
+            };
 
             *pc += 4;
             Ok(true)
         }
         Instruction::RelativeBaseOffset { i1 } => {
-            *relative_base += try_get_param(&program, &relative_base, i1)?;
+            let offset = operands.read(program, i1)?;
+            *relative_base = operands.overflow(arithmetic.add(*relative_base, offset))?;
             *pc += 2;
-            //println!("Relative offset: {}", *relative_base);
             Ok(true)
         }
         Instruction::Halt => {
@@ -331,27 +548,115 @@ where
     }
 }
 
-fn try_get_address(program: &Vec<i64>, relative_base: &i64, param: Parameter) -> Result<usize, String> {
-    match (param.mode, param.value) {
-        (ParameterMode::Position, p) if p >= 0 && p < program.len() as i64 => {
-            Ok(param.value as usize)
-        }
-        (ParameterMode::Relative, p) if *relative_base + p >= 0 => {
-            Ok((*relative_base + param.value) as usize)
+/// Like `process_instruction`, but reports what the instruction did to
+/// `tracer` once it has executed successfully.
+pub fn process_instruction_traced<FIn, FOut, T>(
+    program: &mut Memory,
+    pc: &mut usize,
+    relative_base: &mut i64,
+    arithmetic: Arithmetic,
+    input_fn: &mut FIn,
+    output_fn: &mut FOut,
+    tracer: &mut T,
+) -> Result<bool, IntCodeError>
+where
+    FIn: FnMut() -> Result<i64, Box<dyn Error>>,
+    FOut: FnMut(i64),
+    T: Tracer + ?Sized,
+{
+    let start = *pc;
+    let base = *relative_base;
+    let instruction = decode(program, start)?;
+    let operands = Operands {
+        pc: start,
+        instruction,
+        relative_base: base,
+    };
+
+    // Resolve everything up front; the instruction may overwrite its own inputs.
+    let reads = instruction
+        .read_params()
+        .into_iter()
+        .map(|p| operands.read(program, p))
+        .collect::<Result<Vec<i64>, IntCodeError>>()?;
+    let write = match instruction.write_param() {
+        Some(p) => Some(operands.address(p)?),
+        None => None,
+    };
+
+    let running = process_instruction(program, pc, relative_base, arithmetic, input_fn, output_fn)?;
+
+    tracer.trace(&TraceEvent {
+        pc: start,
+        relative_base: base,
+        instruction,
+        operands: reads,
+        write: write.map(|address| (address, program[address])),
+    });
+
+    Ok(running)
+}
+
+/// Resolves the parameters of one decoded instruction, reporting failures
+/// with the pc and instruction they belong to.
+struct Operands {
+    pc: usize,
+    instruction: Instruction,
+    relative_base: i64,
+}
+
+impl Operands {
+    fn address(&self, param: Parameter) -> Result<usize, IntCodeError> {
+        let address = match param.mode {
+            ParameterMode::Position => param.value,
+            // An address past either end of `i64` is reported as that end.
+            ParameterMode::Relative => match self.relative_base.checked_add(param.value) {
+                Some(address) => address,
+                None => {
+                    return Err(self.out_of_range(self.relative_base.saturating_add(param.value)))
+                }
+            },
+            ParameterMode::Immediate => {
+                return Err(IntCodeError::ImmediateWrite {
+                    pc: self.pc,
+                    instruction: self.instruction,
+                })
+            }
+        };
+
+        self.check(address)
+    }
+
+    fn read(&self, program: &Memory, param: Parameter) -> Result<i64, IntCodeError> {
+        match param.mode {
+            ParameterMode::Immediate => Ok(param.value),
+            _ => Ok(program[self.address(param)?]),
         }
-        _ => Err(format!("Invalid parameter value: {:?}", param)),
     }
-}
 
-fn try_get_param(program: &Vec<i64>, relative_base: &i64, param: Parameter) -> Result<i64, String> {
-    match (param.mode, param.value) {
-        (ParameterMode::Position, p) if p >= 0 && p < program.len() as i64 => {
-            Ok(program[param.value as usize])
+    fn jump_target(&self, program: &Memory, param: Parameter) -> Result<usize, IntCodeError> {
+        self.check(self.read(program, param)?)
+    }
+
+    fn overflow(&self, result: Option<i64>) -> Result<i64, IntCodeError> {
+        result.ok_or(IntCodeError::Overflow {
+            pc: self.pc,
+            instruction: self.instruction,
+        })
+    }
+
+    fn check(&self, address: i64) -> Result<usize, IntCodeError> {
+        match address {
+            a if a >= 0 => Ok(a as usize),
+            _ => Err(self.out_of_range(address)),
         }
-        (ParameterMode::Immediate, _) => Ok(param.value),
-        (ParameterMode::Relative, p) if *relative_base + p >= 0 => {
-            Ok(program[(*relative_base + param.value) as usize])
+    }
+
+    fn out_of_range(&self, address: i64) -> IntCodeError {
+        IntCodeError::AddressOutOfRange {
+            pc: self.pc,
+            instruction: self.instruction,
+            address,
         }
-        _ => Err(format!("Invalid parameter value: {:?}", param)),
     }
 }
@@ -0,0 +1,122 @@
+use super::{err_input, stdout_output};
+use crossbeam_channel::{Receiver, Sender};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io;
+use std::io::{BufRead, Write};
+
+/// Where a machine reads its input from.
+///
+/// Failing with `IntCodeError::InputExhausted`, as `err_input` does, tells the
+/// machine that no more input will come.
+pub trait Input {
+    fn read(&mut self) -> Result<i64, Box<dyn Error>>;
+}
+
+/// Where a machine writes its output to.
+pub trait Output {
+    fn write(&mut self, value: i64) -> Result<(), Box<dyn Error>>;
+}
+
+impl<F> Input for F
+where
+    F: FnMut() -> Result<i64, Box<dyn Error>>,
+{
+    fn read(&mut self) -> Result<i64, Box<dyn Error>> {
+        self()
+    }
+}
+
+impl<F> Output for F
+where
+    F: FnMut(i64),
+{
+    fn write(&mut self, value: i64) -> Result<(), Box<dyn Error>> {
+        self(value);
+        Ok(())
+    }
+}
+
+impl Input for VecDeque<i64> {
+    fn read(&mut self) -> Result<i64, Box<dyn Error>> {
+        match self.pop_front() {
+            Some(value) => Ok(value),
+            None => err_input(),
+        }
+    }
+}
+
+impl Output for VecDeque<i64> {
+    fn write(&mut self, value: i64) -> Result<(), Box<dyn Error>> {
+        self.push_back(value);
+        Ok(())
+    }
+}
+
+impl Output for Vec<i64> {
+    fn write(&mut self, value: i64) -> Result<(), Box<dyn Error>> {
+        self.push(value);
+        Ok(())
+    }
+}
+
+/// Blocks until a value arrives. Once every sender is gone the input is
+/// exhausted.
+impl Input for Receiver<i64> {
+    fn read(&mut self) -> Result<i64, Box<dyn Error>> {
+        match self.recv() {
+            Ok(value) => Ok(value),
+            Err(_) => err_input(),
+        }
+    }
+}
+
+/// Fails once the receiver is gone.
+impl Output for Sender<i64> {
+    fn write(&mut self, value: i64) -> Result<(), Box<dyn Error>> {
+        Ok(self.send(value)?)
+    }
+}
+
+/// Reads input from an iterator, see `iter_input`.
+#[derive(Debug, Clone)]
+pub struct IterInput<I>(I);
+
+/// Feeds the values of `values` to a machine, in order.
+pub fn iter_input<I: IntoIterator<Item = i64>>(values: I) -> IterInput<I::IntoIter> {
+    IterInput(values.into_iter())
+}
+
+impl<I: Iterator<Item = i64>> Input for IterInput<I> {
+    fn read(&mut self) -> Result<i64, Box<dyn Error>> {
+        match self.0.next() {
+            Some(value) => Ok(value),
+            None => err_input(),
+        }
+    }
+}
+
+/// Interactive I/O: prompts for each input value on stdin and prints every
+/// output value. The input ends at the end of stdin.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Console;
+
+impl Input for Console {
+    fn read(&mut self) -> Result<i64, Box<dyn Error>> {
+        print!("INPUT: ");
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line)? {
+            0 => err_input(),
+            _ => Ok(line.trim().parse()?),
+        }
+    }
+}
+
+impl Output for Console {
+    fn write(&mut self, value: i64) -> Result<(), Box<dyn Error>> {
+        stdout_output(value);
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! ASCII mode: programs that read and write text one character code at a
+//! time, with the odd value outside ASCII, such as a puzzle answer, mixed in.
+
+use super::{err_input, Input, Output};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+
+const NEWLINE: i64 = b'\n' as i64;
+
+/// Encodes `line` as character codes, ending with a newline.
+pub fn encode(line: &str) -> Vec<i64> {
+    let mut values: Vec<i64> = line.bytes().map(i64::from).collect();
+    if values.last() != Some(&NEWLINE) {
+        values.push(NEWLINE);
+    }
+    values
+}
+
+/// Splits a whole output stream into lines and values, see `Lines`.
+pub fn decode(values: &[i64]) -> Vec<Chunk> {
+    let mut lines = Lines::new();
+    for &value in values {
+        lines.push(value);
+    }
+    lines.finish();
+    lines.chunks.into_iter().collect()
+}
+
+/// A piece of ASCII output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Chunk {
+    /// A line of text, without its newline.
+    Line(String),
+    /// A value that isn't an ASCII character.
+    Value(i64),
+}
+
+impl fmt::Display for Chunk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Chunk::Line(line) => write!(f, "{}", line),
+            Chunk::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Buffers output into lines. Values from 0 to 127 are characters and a
+/// newline completes the line; anything else is passed through as is,
+/// without breaking the line being buffered.
+#[derive(Debug, Clone, Default)]
+pub struct Lines {
+    line: String,
+    chunks: VecDeque<Chunk>,
+}
+
+impl Lines {
+    pub fn new() -> Self {
+        Lines::default()
+    }
+
+    pub fn push(&mut self, value: i64) {
+        match value {
+            NEWLINE => {
+                let line = self.take_partial();
+                self.chunks.push_back(Chunk::Line(line));
+            }
+            0..=127 => self.line.push(value as u8 as char),
+            _ => self.chunks.push_back(Chunk::Value(value)),
+        }
+    }
+
+    /// The next complete line or value, in output order.
+    pub fn pop(&mut self) -> Option<Chunk> {
+        self.chunks.pop_front()
+    }
+
+    /// The text after the last newline, typically a prompt.
+    pub fn partial(&self) -> &str {
+        &self.line
+    }
+
+    /// Takes the text after the last newline, leaving the next output to
+    /// start a new line.
+    pub fn take_partial(&mut self) -> String {
+        self.line.split_off(0)
+    }
+
+    /// Ends the output: the text after the last newline, if any, becomes a
+    /// line of its own.
+    pub fn finish(&mut self) {
+        if !self.line.is_empty() {
+            let line = self.take_partial();
+            self.chunks.push_back(Chunk::Line(line));
+        }
+    }
+}
+
+impl Output for Lines {
+    fn write(&mut self, value: i64) -> Result<(), Box<dyn Error>> {
+        self.push(value);
+        Ok(())
+    }
+}
+
+/// Reads input a line at a time from `reader`, feeding it to the machine as
+/// character codes. The input ends with the reader.
+#[derive(Debug)]
+pub struct LineInput<R> {
+    reader: R,
+    pending: VecDeque<i64>,
+}
+
+impl<R: BufRead> LineInput<R> {
+    pub fn new(reader: R) -> Self {
+        LineInput {
+            reader,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<R: BufRead> Input for LineInput<R> {
+    fn read(&mut self) -> Result<i64, Box<dyn Error>> {
+        if self.pending.is_empty() {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return err_input();
+            }
+            self.pending
+                .extend(encode(line.trim_end_matches(&['\r', '\n'][..])));
+        }
+        Ok(self.pending.pop_front().unwrap())
+    }
+}
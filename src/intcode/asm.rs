@@ -0,0 +1,255 @@
+//! A small assembler for Intcode.
+//!
+//! Each line holds an optional `label:`, then either an instruction or a
+//! `.data` directive. Everything after `;` is a comment.
+//!
+//! ```text
+//! start:  IN   [n]
+//!         MUL  [n], #2, [n]
+//!         OUT  [n]
+//!         JT   #1, #start
+//! n:      .data 0
+//! ```
+//!
+//! Operands are `[a]` for position mode, `#a` for immediate mode and `rb+a`
+//! or `rb-n` for relative mode. `a` is a number, a label, or a label plus or
+//! minus a number; `n` is a number.
+
+use super::ParameterMode;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsmError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl AsmError {
+    fn new<T: AsRef<str>>(line: usize, msg: T) -> Self {
+        AsmError {
+            line,
+            message: msg.as_ref().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AsmError {}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Number(i64),
+    Label(String, i64),
+}
+
+#[derive(Debug, Clone)]
+struct Operand {
+    mode: ParameterMode,
+    value: Value,
+}
+
+#[derive(Debug)]
+enum Statement {
+    Instruction { opcode: i64, operands: Vec<Operand> },
+    Data(Vec<Value>),
+}
+
+/// Opcode, operand count and index of the written operand for a mnemonic.
+fn opcode(mnemonic: &str) -> Option<(i64, usize, Option<usize>)> {
+    match mnemonic.to_uppercase().as_str() {
+        "ADD" => Some((1, 3, Some(2))),
+        "MUL" => Some((2, 3, Some(2))),
+        "IN" => Some((3, 1, Some(0))),
+        "OUT" => Some((4, 1, None)),
+        "JT" => Some((5, 2, None)),
+        "JF" => Some((6, 2, None)),
+        "LT" => Some((7, 3, Some(2))),
+        "EQ" => Some((8, 3, Some(2))),
+        "ARB" => Some((9, 1, None)),
+        "HLT" => Some((99, 0, None)),
+        _ => None,
+    }
+}
+
+/// Assembles `source` into Intcode words.
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+    let mut labels: HashMap<String, i64> = HashMap::new();
+    let mut statements: Vec<(usize, Statement)> = Vec::new();
+    let mut address = 0;
+
+    for (i, raw) in source.lines().enumerate() {
+        let line = i + 1;
+        let mut text = raw.split(';').next().unwrap_or("").trim();
+
+        while let Some(colon) = text.find(':') {
+            let label = text[..colon].trim();
+            if !is_identifier(label) {
+                return Err(AsmError::new(line, format!("Invalid label '{}'", label)));
+            }
+            if labels.insert(label.to_string(), address).is_some() {
+                return Err(AsmError::new(line, format!("Duplicate label '{}'", label)));
+            }
+            text = text[colon + 1..].trim();
+        }
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let (head, rest) = match text.find(char::is_whitespace) {
+            Some(i) => (&text[..i], text[i..].trim()),
+            None => (text, ""),
+        };
+        let args: Vec<&str> = match rest.is_empty() {
+            true => Vec::new(),
+            false => rest.split(',').map(str::trim).collect(),
+        };
+
+        let statement = if head.eq_ignore_ascii_case(".data") {
+            let values = args
+                .iter()
+                .map(|a| {
+                    parse_value(a)
+                        .ok_or_else(|| AsmError::new(line, format!("Invalid value '{}'", a)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Statement::Data(values)
+        } else {
+            let (opcode, count, writes) = opcode(head)
+                .ok_or_else(|| AsmError::new(line, format!("Unknown mnemonic '{}'", head)))?;
+            if args.len() != count {
+                return Err(AsmError::new(
+                    line,
+                    format!("{} takes {} operands, got {}", head, count, args.len()),
+                ));
+            }
+            let operands = args
+                .iter()
+                .map(|a| {
+                    parse_operand(a)
+                        .ok_or_else(|| AsmError::new(line, format!("Invalid operand '{}'", a)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(w) = writes {
+                if operands[w].mode == ParameterMode::Immediate {
+                    return Err(AsmError::new(
+                        line,
+                        format!("{} cannot write to an immediate operand", head),
+                    ));
+                }
+            }
+            Statement::Instruction { opcode, operands }
+        };
+
+        address += match &statement {
+            Statement::Instruction { operands, .. } => 1 + operands.len() as i64,
+            Statement::Data(values) => values.len() as i64,
+        };
+        statements.push((line, statement));
+    }
+
+    let resolve = |line: usize, value: &Value| -> Result<i64, AsmError> {
+        match value {
+            Value::Number(n) => Ok(*n),
+            Value::Label(name, offset) => labels
+                .get(name)
+                .map(|a| a + offset)
+                .ok_or_else(|| AsmError::new(line, format!("Unknown label '{}'", name))),
+        }
+    };
+
+    let mut words = Vec::new();
+    for (line, statement) in statements {
+        match statement {
+            Statement::Instruction { opcode, operands } => {
+                let modes = operands.iter().enumerate().fold(0, |m, (i, o)| {
+                    m + (o.mode as i64) * 10_i64.pow(i as u32 + 2)
+                });
+                words.push(opcode + modes);
+                for operand in operands {
+                    words.push(resolve(line, &operand.value)?);
+                }
+            }
+            Statement::Data(values) => {
+                for value in values {
+                    words.push(resolve(line, &value)?);
+                }
+            }
+        }
+    }
+
+    Ok(words)
+}
+
+/// Formats words the way `parser::parse_comma_list` reads them.
+pub fn to_comma_list(words: &[i64]) -> String {
+    words
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+fn parse_operand(s: &str) -> Option<Operand> {
+    if s.starts_with('[') && s.ends_with(']') {
+        return Some(Operand {
+            mode: ParameterMode::Position,
+            value: parse_value(&s[1..s.len() - 1])?,
+        });
+    }
+
+    if let Some(rest) = s.strip_prefix('#') {
+        return Some(Operand {
+            mode: ParameterMode::Immediate,
+            value: parse_value(rest)?,
+        });
+    }
+
+    if s.len() > 2 && s.get(..2).is_some_and(|p| p.eq_ignore_ascii_case("rb")) {
+        let rest = s[2..].trim();
+        let value = match rest.strip_prefix('+') {
+            Some(r) => parse_value(r)?,
+            None => Value::Number(rest.parse().ok()?),
+        };
+        return Some(Operand {
+            mode: ParameterMode::Relative,
+            value,
+        });
+    }
+
+    None
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    let s = s.trim();
+    if let Ok(n) = s.parse::<i64>() {
+        return Some(Value::Number(n));
+    }
+
+    let (name, offset) = match s.rfind(['+', '-']) {
+        Some(i) if i > 0 => (s[..i].trim(), s[i..].replace(' ', "").parse::<i64>().ok()?),
+        _ => (s, 0),
+    };
+
+    match is_identifier(name) {
+        true => Some(Value::Label(name.to_string(), offset)),
+        false => None,
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
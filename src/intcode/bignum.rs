@@ -0,0 +1,214 @@
+use super::memory::Pages;
+use super::{decode_with, Engine, Instruction, IntCodeError, Parameter, ParameterMode, StepResult};
+use num::{BigInt, ToPrimitive, Zero};
+use std::collections::VecDeque;
+
+const INPUT_OPCODE: i64 = 3;
+
+/// An Intcode VM whose words are arbitrary-precision integers, so no result
+/// ever overflows.
+///
+/// It is far slower than a `Machine` and meant for checking results: if a
+/// program's output on a `Machine` matches its output here, no overflow went
+/// unnoticed. Otherwise it behaves the same, with the input and output
+/// queues of `Machine::run`.
+#[derive(Debug, Clone, Default)]
+pub struct BigMachine {
+    memory: Pages<BigInt>,
+    pc: usize,
+    relative_base: BigInt,
+    halted: bool,
+    input: VecDeque<BigInt>,
+    output: VecDeque<BigInt>,
+}
+
+impl BigMachine {
+    pub fn new(program: &[i64]) -> Self {
+        let mut machine = BigMachine::default();
+        for (address, &word) in program.iter().enumerate() {
+            machine.set(address, word);
+        }
+        machine
+    }
+
+    pub fn get(&self, address: usize) -> BigInt {
+        self.word(address).cloned().unwrap_or_else(BigInt::zero)
+    }
+
+    pub fn set<V: Into<BigInt>>(&mut self, address: usize, value: V) {
+        *self.memory.get_mut(address) = value.into();
+    }
+
+    /// Copies the words in `[0, len)` into a vector, leaving out the sparse
+    /// ones like `Memory::to_vec`.
+    pub fn to_vec(&self) -> Vec<BigInt> {
+        (0..self.memory.paged_len())
+            .map(|address| self.get(address))
+            .collect()
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn relative_base(&self) -> &BigInt {
+        &self.relative_base
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn push_input<V: Into<BigInt>>(&mut self, value: V) {
+        self.input.push_back(value.into());
+    }
+
+    pub fn pop_output(&mut self) -> Option<BigInt> {
+        self.output.pop_front()
+    }
+
+    pub fn take_output(&mut self) -> Vec<BigInt> {
+        self.output.drain(..).collect()
+    }
+
+    fn word(&self, address: usize) -> Option<&BigInt> {
+        self.memory.get(address)
+    }
+
+    /// Executes a single instruction, with the same results as
+    /// `Machine::step`.
+    pub fn step(&mut self) -> Result<StepResult<BigInt>, IntCodeError> {
+        if self.halted {
+            return Ok(StepResult::Halted);
+        }
+
+        // Words too big for an `i64` are never valid instructions; saturating
+        // them makes `decode` reject them. Parameter values are read from
+        // memory below, at full precision.
+        let pc = self.pc;
+        let small = |address| self.word(address).map_or(0, saturate);
+        if self.input.is_empty() && small(pc) % 100 == INPUT_OPCODE {
+            return Ok(StepResult::NeedInput);
+        }
+        let instruction = decode_with(small, pc)?;
+        let at = At { pc, instruction };
+
+        // As in `process_instruction`, the written address is resolved first.
+        let write = match instruction.write_param() {
+            Some(param) => Some(self.address(&at, param, instruction.size() - 1)?),
+            None => None,
+        };
+        // A jump target is only read if the jump is taken.
+        let params = instruction.read_params();
+        let eager = match instruction {
+            Instruction::JumpIfTrue { .. } | Instruction::JumpIfFalse { .. } => 1,
+            _ => params.len(),
+        };
+        let reads = params[..eager]
+            .iter()
+            .enumerate()
+            .map(|(n, &param)| self.read(&at, param, n + 1))
+            .collect::<Result<Vec<BigInt>, IntCodeError>>()?;
+
+        let mut next = pc + instruction.size();
+        let mut result = StepResult::Continue;
+        let value = match instruction {
+            Instruction::Sum { .. } => Some(&reads[0] + &reads[1]),
+            Instruction::Prod { .. } => Some(&reads[0] * &reads[1]),
+            Instruction::LessThan { .. } => Some(BigInt::from((reads[0] < reads[1]) as i64)),
+            Instruction::Equals { .. } => Some(BigInt::from((reads[0] == reads[1]) as i64)),
+            Instruction::Input { .. } => self.input.pop_front(),
+            Instruction::Output { .. } => {
+                result = StepResult::Output(reads[0].clone());
+                None
+            }
+            Instruction::JumpIfTrue { .. } | Instruction::JumpIfFalse { .. } => {
+                let jump = match instruction {
+                    Instruction::JumpIfTrue { .. } => !reads[0].is_zero(),
+                    _ => reads[0].is_zero(),
+                };
+                if jump {
+                    next = at.address(&self.read(&at, params[1], 2)?)?;
+                }
+                None
+            }
+            Instruction::RelativeBaseOffset { .. } => {
+                self.relative_base += &reads[0];
+                None
+            }
+            Instruction::Halt => {
+                self.halted = true;
+                result = StepResult::Halted;
+                None
+            }
+        };
+
+        if let (Some(address), Some(value)) = (write, value) {
+            self.set(address, value);
+        }
+        self.pc = next;
+        Ok(result)
+    }
+
+    /// The address parameter `n` of the instruction refers to.
+    fn address(&self, at: &At, param: Parameter, n: usize) -> Result<usize, IntCodeError> {
+        let word = self.get(at.pc + n);
+        match param.mode {
+            ParameterMode::Relative => at.address(&(&self.relative_base + word)),
+            _ => at.address(&word),
+        }
+    }
+
+    fn read(&self, at: &At, param: Parameter, n: usize) -> Result<BigInt, IntCodeError> {
+        match param.mode {
+            ParameterMode::Immediate => Ok(self.get(at.pc + n)),
+            _ => Ok(self.get(self.address(at, param, n)?)),
+        }
+    }
+}
+
+impl Engine for BigMachine {
+    type Word = BigInt;
+
+    fn step(&mut self) -> Result<StepResult<BigInt>, IntCodeError> {
+        BigMachine::step(self)
+    }
+
+    fn pc(&self) -> usize {
+        self.pc
+    }
+
+    fn push_output(&mut self, value: BigInt) {
+        self.output.push_back(value);
+    }
+
+    fn pop_output(&mut self) -> Option<BigInt> {
+        self.output.pop_front()
+    }
+}
+
+/// The instruction being executed, for error reports.
+struct At {
+    pc: usize,
+    instruction: Instruction,
+}
+
+impl At {
+    fn address(&self, address: &BigInt) -> Result<usize, IntCodeError> {
+        address
+            .to_usize()
+            .ok_or_else(|| IntCodeError::AddressOutOfRange {
+                pc: self.pc,
+                instruction: self.instruction,
+                address: saturate(address),
+            })
+    }
+}
+
+fn saturate(value: &BigInt) -> i64 {
+    match value.to_i64() {
+        Some(value) => value,
+        None if value > &BigInt::zero() => i64::MAX,
+        None => i64::MIN,
+    }
+}
@@ -0,0 +1,440 @@
+//! An ahead-of-time compiler from Intcode to Rust.
+//!
+//! `to_rust` turns a program into the source of a function
+//!
+//! ```text
+//! pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError>
+//! ```
+//!
+//! that behaves like `FastMachine::run`. Every instruction reachable from
+//! address 0 through jumps with immediate targets becomes plain Rust, with one
+//! match arm per jump target. Whenever the compiled code can't go on, it
+//! hands the machine over to the interpreter at the instruction it stopped
+//! at:
+//!
+//! * on a computed jump to an address that isn't a compiled jump target,
+//! * after a write into a compiled instruction,
+//! * when input runs out, an address is negative, a result overflows or the
+//!   program halts, so the interpreter deals with it exactly as it would
+//!   have.
+//!
+//! The machine must hold the program it was compiled from, except for the
+//! `variables` words: those are read from memory every time instead of being
+//! compiled in, so they can be patched before each run, like the noun and
+//! verb of day 2.
+
+use super::{decode, Instruction, Memory, Parameter, ParameterMode};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::fmt::Write;
+
+const INDENT: &str = "                ";
+
+/// Returns the Rust source of a `run` function executing `program`.
+pub fn to_rust(program: &[i64], variables: &[usize]) -> String {
+    let compiler = Compiler::new(program, variables);
+    let mut out = String::new();
+    compiler.emit(&mut out).unwrap();
+    out
+}
+
+struct Compiler {
+    memory: Memory,
+    variables: HashSet<usize>,
+    /// Every instruction reachable from address 0, by address.
+    code: BTreeMap<usize, Instruction>,
+    /// Addresses execution reaches other than by falling through, each of
+    /// which gets a match arm.
+    entries: BTreeSet<usize>,
+    /// The words holding compiled instructions as sorted `[start, end)`
+    /// ranges. Variable words are left out, writing them is fine.
+    ranges: Vec<(usize, usize)>,
+}
+
+/// A parameter resolved for the generated code.
+struct Operand {
+    /// Expression for the value the parameter reads.
+    read: String,
+    /// The address the parameter writes to.
+    write: Option<Address>,
+}
+
+enum Address {
+    Constant(usize),
+    /// Held in a variable, already checked not to be negative.
+    Dynamic(String),
+}
+
+impl Compiler {
+    fn new(program: &[i64], variables: &[usize]) -> Self {
+        let mut compiler = Compiler {
+            memory: Memory::from(program),
+            variables: variables.iter().cloned().collect(),
+            code: BTreeMap::new(),
+            entries: BTreeSet::new(),
+            ranges: Vec::new(),
+        };
+        compiler.find_code();
+        compiler.ranges = compiler.code_ranges();
+        compiler
+    }
+
+    /// Follows the control flow from address 0 like
+    /// `disasm::follow_control_flow`, noting the targets of jumps and the
+    /// addresses following them as entries.
+    fn find_code(&mut self) {
+        let mut pending = vec![0];
+        self.entries.insert(0);
+
+        while let Some(address) = pending.pop() {
+            if self.code.contains_key(&address) {
+                continue;
+            }
+            let instruction = match self.decode(address) {
+                Some(instruction) => instruction,
+                None => continue,
+            };
+            self.code.insert(address, instruction);
+
+            let next = address + instruction.size();
+            match instruction {
+                Instruction::Halt => {}
+                Instruction::JumpIfTrue { pc, .. } | Instruction::JumpIfFalse { pc, .. } => {
+                    if let Some(target) = self.jump_target(address, pc) {
+                        self.entries.insert(target);
+                        pending.push(target);
+                    }
+                    self.entries.insert(next);
+                    pending.push(next);
+                }
+                _ => pending.push(next),
+            }
+        }
+    }
+
+    /// The instruction at `address`, if it can be compiled: it decodes, lies
+    /// within the program and its opcode isn't variable.
+    fn decode(&self, address: usize) -> Option<Instruction> {
+        if self.variables.contains(&address) {
+            return None;
+        }
+        let instruction = decode(&self.memory, address).ok()?;
+        match address + instruction.size() <= self.memory.len() {
+            true => Some(instruction),
+            false => None,
+        }
+    }
+
+    /// The target of the jump at `address`, if it is known at compile time.
+    fn jump_target(&self, address: usize, pc: Parameter) -> Option<usize> {
+        match pc.mode {
+            ParameterMode::Immediate if !self.variables.contains(&(address + 2)) => {
+                match pc.value {
+                    v if v >= 0 => Some(v as usize),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn code_ranges(&self) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        let words: BTreeSet<usize> = self
+            .code
+            .iter()
+            .flat_map(|(&address, instruction)| address..address + instruction.size())
+            .filter(|word| !self.variables.contains(word))
+            .collect();
+        for word in words {
+            match ranges.last_mut() {
+                Some(range) if range.1 == word => range.1 += 1,
+                _ => ranges.push((word, word + 1)),
+            }
+        }
+        ranges
+    }
+
+    /// Whether an instruction compiled with `word` in it may still run once
+    /// execution goes on at `from`. Writing to it has to leave the rest to
+    /// the interpreter then; writes to code that already ran, like day 2's
+    /// programs do all the time, are fine.
+    fn may_run(&self, from: usize, word: usize) -> bool {
+        if self.variables.contains(&word) {
+            return false;
+        }
+
+        let mut seen = HashSet::new();
+        let mut pending = vec![from];
+        while let Some(address) = pending.pop() {
+            let instruction = match self.code.get(&address) {
+                Some(&instruction) if seen.insert(address) => instruction,
+                _ => continue,
+            };
+            if address <= word && word < address + instruction.size() {
+                return true;
+            }
+
+            let next = address + instruction.size();
+            match instruction {
+                Instruction::Halt => {}
+                Instruction::JumpIfTrue { pc, .. } | Instruction::JumpIfFalse { pc, .. } => {
+                    pending.push(next);
+                    match self.jump_target(address, pc) {
+                        Some(target) => pending.push(target),
+                        None => pending.extend(self.entries.iter().cloned()),
+                    }
+                }
+                _ => pending.push(next),
+            }
+        }
+        false
+    }
+
+    fn emit(&self, out: &mut String) -> fmt::Result {
+        let ranges: Vec<String> = self
+            .ranges
+            .iter()
+            .map(|(start, end)| format!("({}, {})", start, end))
+            .collect();
+
+        writeln!(out, "// Generated by intcode-compile, do not edit.")?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "#![allow(dead_code, unused_assignments, unused_mut, unreachable_code, clippy::all)]"
+        )?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "use advent_of_code_2019::intcode::{{Engine, FastMachine, IntCodeError}};"
+        )?;
+        writeln!(out)?;
+        writeln!(out, "/// The words holding compiled instructions.")?;
+        writeln!(out, "const CODE: &[(i64, i64)] = &[{}];", ranges.join(", "))?;
+        writeln!(out)?;
+        writeln!(out, "fn in_code(address: i64) -> bool {{")?;
+        writeln!(
+            out,
+            "    CODE.iter().any(|&(start, end)| start <= address && address < end)"
+        )?;
+        writeln!(out, "}}")?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError> {{"
+        )?;
+        writeln!(out, "    if m.is_halted() {{")?;
+        writeln!(out, "        return Ok(());")?;
+        writeln!(out, "    }}")?;
+        writeln!(out, "    let mut pc = m.pc();")?;
+        writeln!(out, "    let mut rb = m.relative_base();")?;
+        writeln!(out, "    'run: loop {{")?;
+        writeln!(out, "        match pc {{")?;
+        for &entry in self.entries.iter().filter(|a| self.code.contains_key(a)) {
+            writeln!(out, "            {} => {{", entry)?;
+            self.emit_block(out, entry)?;
+            writeln!(out, "            }}")?;
+        }
+        writeln!(out, "            _ => break 'run,")?;
+        writeln!(out, "        }}")?;
+        writeln!(out, "    }}")?;
+        writeln!(out, "    m.set_pc(pc);")?;
+        writeln!(out, "    m.set_relative_base(rb);")?;
+        writeln!(out, "    m.run()")?;
+        writeln!(out, "}}")
+    }
+
+    /// Emits the instructions from `entry` on, until one of them leaves the
+    /// block or the next one is an entry of its own.
+    fn emit_block(&self, out: &mut String, entry: usize) -> fmt::Result {
+        let mut address = entry;
+        loop {
+            let instruction = self.code[&address];
+            writeln!(out, "{}// {}: {}", INDENT, address, instruction)?;
+            writeln!(out, "{}pc = {};", INDENT, address)?;
+            if !self.emit_instruction(out, address, instruction)? {
+                return Ok(());
+            }
+
+            let next = address + instruction.size();
+            if self.entries.contains(&next) || !self.code.contains_key(&next) {
+                return self.emit_goto(out, INDENT, next);
+            }
+            address = next;
+        }
+    }
+
+    /// Emits one instruction. Returns false if it never falls through to the
+    /// next one.
+    fn emit_instruction(
+        &self,
+        out: &mut String,
+        address: usize,
+        instruction: Instruction,
+    ) -> Result<bool, fmt::Error> {
+        let next = address + instruction.size();
+
+        // Every address is resolved and checked before anything changes, so
+        // that the interpreter can take over at this instruction.
+        let mut operands = Vec::new();
+        for (n, param) in instruction.params().into_iter().enumerate() {
+            match self.operand(out, address + n + 1, param, n)? {
+                Some(operand) => operands.push(operand),
+                None => {
+                    writeln!(out, "{}break 'run;", INDENT)?;
+                    return Ok(false);
+                }
+            }
+        }
+        let read = |n: usize| operands[n].read.as_str();
+
+        let (destination, value) = match instruction {
+            Instruction::Sum { .. } => {
+                emit_checked(out, "let value", "checked_add", read(0), read(1))?;
+                (2, "value".to_string())
+            }
+            Instruction::Prod { .. } => {
+                emit_checked(out, "let value", "checked_mul", read(0), read(1))?;
+                (2, "value".to_string())
+            }
+            Instruction::LessThan { .. } => (2, format!("({} < {}) as i64", read(0), read(1))),
+            Instruction::Equals { .. } => (2, format!("({} == {}) as i64", read(0), read(1))),
+            Instruction::Input { .. } => {
+                writeln!(out, "{}let value = match m.pop_input() {{", INDENT)?;
+                writeln!(out, "{}    Some(value) => value,", INDENT)?;
+                writeln!(out, "{}    None => break 'run,", INDENT)?;
+                writeln!(out, "{}}};", INDENT)?;
+                (0, "value".to_string())
+            }
+            Instruction::Output { .. } => {
+                writeln!(out, "{}m.push_output({});", INDENT, read(0))?;
+                return Ok(true);
+            }
+            Instruction::RelativeBaseOffset { .. } => {
+                emit_checked(out, "rb", "checked_add", "rb", read(0))?;
+                return Ok(true);
+            }
+            Instruction::JumpIfTrue { pc, .. } | Instruction::JumpIfFalse { pc, .. } => {
+                let comparison = match instruction {
+                    Instruction::JumpIfTrue { .. } => "!=",
+                    _ => "==",
+                };
+                let inner = format!("{}    ", INDENT);
+                writeln!(out, "{}if {} {} 0 {{", INDENT, read(0), comparison)?;
+                match self.jump_target(address, pc) {
+                    Some(target) => self.emit_goto(out, &inner, target)?,
+                    None => {
+                        writeln!(out, "{}let target = {};", inner, read(1))?;
+                        emit_check(out, &inner, "target")?;
+                        writeln!(out, "{}pc = target as usize;", inner)?;
+                        writeln!(out, "{}continue 'run;", inner)?;
+                    }
+                }
+                writeln!(out, "{}}}", INDENT)?;
+                self.emit_goto(out, INDENT, next)?;
+                return Ok(false);
+            }
+            Instruction::Halt => {
+                writeln!(out, "{}break 'run;", INDENT)?;
+                return Ok(false);
+            }
+        };
+
+        match operands[destination].write {
+            Some(Address::Constant(a)) => {
+                writeln!(out, "{}m.set({}, {});", INDENT, a, value)?;
+                if self.may_run(next, a) {
+                    writeln!(out, "{}pc = {};", INDENT, next)?;
+                    writeln!(out, "{}break 'run;", INDENT)?;
+                    return Ok(false);
+                }
+            }
+            Some(Address::Dynamic(ref a)) => {
+                writeln!(out, "{}m.set({} as usize, {});", INDENT, a, value)?;
+                writeln!(out, "{}if in_code({}) {{", INDENT, a)?;
+                writeln!(out, "{}    pc = {};", INDENT, next)?;
+                writeln!(out, "{}    break 'run;", INDENT)?;
+                writeln!(out, "{}}}", INDENT)?;
+            }
+            None => unreachable!("decode rejects immediate writes"),
+        }
+        Ok(true)
+    }
+
+    /// Resolves parameter `n`, held in `word`, emitting the code computing
+    /// and checking its address if that isn't known at compile time. Returns
+    /// `None` if the address is known to be negative.
+    fn operand(
+        &self,
+        out: &mut String,
+        word: usize,
+        param: Parameter,
+        n: usize,
+    ) -> Result<Option<Operand>, fmt::Error> {
+        let variable = self.variables.contains(&word);
+        let value = match variable {
+            true => format!("m.get({})", word),
+            false => param.value.to_string(),
+        };
+
+        let name = format!("a{}", n + 1);
+        match (param.mode, variable) {
+            (ParameterMode::Immediate, _) => {
+                return Ok(Some(Operand {
+                    read: value,
+                    write: None,
+                }))
+            }
+            (ParameterMode::Position, false) if param.value < 0 => return Ok(None),
+            (ParameterMode::Position, false) => {
+                let address = param.value as usize;
+                return Ok(Some(Operand {
+                    read: format!("m.get({})", address),
+                    write: Some(Address::Constant(address)),
+                }));
+            }
+            (ParameterMode::Position, true) => {
+                writeln!(out, "{}let {} = {};", INDENT, name, value)?;
+            }
+            (ParameterMode::Relative, _) => {
+                let target = format!("let {}", name);
+                emit_checked(out, &target, "checked_add", "rb", &value)?;
+            }
+        }
+        emit_check(out, INDENT, &name)?;
+        Ok(Some(Operand {
+            read: format!("m.get({} as usize)", name),
+            write: Some(Address::Dynamic(name)),
+        }))
+    }
+
+    /// Continues at `target`, compiled if it is an entry.
+    fn emit_goto(&self, out: &mut String, indent: &str, target: usize) -> fmt::Result {
+        writeln!(out, "{}pc = {};", indent, target)?;
+        match self.entries.contains(&target) && self.code.contains_key(&target) {
+            true => writeln!(out, "{}continue 'run;", indent),
+            false => writeln!(out, "{}break 'run;", indent),
+        }
+    }
+}
+
+/// Assigns the result of `i64::<method>(a, b)` to `target`, leaving overflow
+/// to the interpreter, which deals with it as its `Arithmetic` says.
+fn emit_checked(out: &mut String, target: &str, method: &str, a: &str, b: &str) -> fmt::Result {
+    writeln!(
+        out,
+        "{}{} = match i64::{}({}, {}) {{",
+        INDENT, target, method, a, b
+    )?;
+    writeln!(out, "{}    Some(value) => value,", INDENT)?;
+    writeln!(out, "{}    None => break 'run,", INDENT)?;
+    writeln!(out, "{}}};", INDENT)
+}
+
+/// Leaves negative addresses to the interpreter, which reports them.
+fn emit_check(out: &mut String, indent: &str, name: &str) -> fmt::Result {
+    writeln!(out, "{}if {} < 0 {{", indent, name)?;
+    writeln!(out, "{}    break 'run;", indent)?;
+    writeln!(out, "{}}}", indent)
+}
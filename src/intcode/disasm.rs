@@ -0,0 +1,123 @@
+use super::{decode, Instruction, Memory, ParameterMode};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// One line of a listing: either a decoded instruction or a single data word.
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub address: usize,
+    pub words: Vec<i64>,
+    pub instruction: Option<Instruction>,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let words: Vec<String> = self.words.iter().map(i64::to_string).collect();
+        write!(f, "{:>6}: {:<28}", self.address, words.join(","))?;
+        match self.instruction {
+            Some(instruction) => write!(f, "{}", instruction),
+            None => write!(f, "{:<5}{}", "DATA", self.words[0]),
+        }
+    }
+}
+
+/// Disassembles the whole program front to back, treating every word that
+/// does not decode as data.
+pub fn linear(program: &Memory) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let line = line_at(program, address);
+        address += line.words.len();
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Disassembles only the instructions reachable from address 0 by following
+/// jumps with immediate targets. Everything else, including code that is
+/// only reached through computed jumps, is listed as data.
+pub fn follow_control_flow(program: &Memory) -> Vec<Line> {
+    let mut code: BTreeSet<usize> = BTreeSet::new();
+    let mut seen: HashSet<usize> = HashSet::new();
+    let mut pending = vec![0];
+
+    while let Some(address) = pending.pop() {
+        if address >= program.len() || !seen.insert(address) {
+            continue;
+        }
+
+        let line = match decode_line(program, address) {
+            Some(line) => line,
+            None => continue,
+        };
+        code.insert(address);
+
+        let next = address + line.words.len();
+        match line.instruction {
+            Some(Instruction::Halt) | None => {}
+            Some(Instruction::JumpIfTrue { i1, pc })
+            | Some(Instruction::JumpIfFalse { i1, pc }) => {
+                if pc.mode == ParameterMode::Immediate && pc.value >= 0 {
+                    pending.push(pc.value as usize);
+                }
+                // A constant condition makes one of the branches unreachable.
+                let always = match (line.instruction, i1.mode) {
+                    (Some(Instruction::JumpIfTrue { .. }), ParameterMode::Immediate) => {
+                        i1.value != 0
+                    }
+                    (Some(Instruction::JumpIfFalse { .. }), ParameterMode::Immediate) => {
+                        i1.value == 0
+                    }
+                    _ => false,
+                };
+                if !always {
+                    pending.push(next);
+                }
+            }
+            Some(_) => pending.push(next),
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let line = match code.contains(&address) {
+            true => decode_line(program, address).unwrap(),
+            false => data_line(program, address),
+        };
+        address += line.words.len();
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Disassembles the single instruction at `address`, or a data word if it
+/// does not decode.
+pub fn line_at(program: &Memory, address: usize) -> Line {
+    decode_line(program, address).unwrap_or_else(|| data_line(program, address))
+}
+
+fn decode_line(program: &Memory, address: usize) -> Option<Line> {
+    let instruction = decode(program, address).ok()?;
+    let end = address + instruction.size();
+    if end > program.len() {
+        return None;
+    }
+
+    Some(Line {
+        address,
+        words: (address..end).map(|a| program[a]).collect(),
+        instruction: Some(instruction),
+    })
+}
+
+fn data_line(program: &Memory, address: usize) -> Line {
+    Line {
+        address,
+        words: vec![program[address]],
+        instruction: None,
+    }
+}
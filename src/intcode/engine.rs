@@ -0,0 +1,58 @@
+use super::{IntCodeError, StepResult};
+
+/// The run loops shared by every Intcode engine, built on its `step`.
+pub trait Engine {
+    /// What a memory word, and so an output value, is.
+    type Word;
+
+    /// Executes a single instruction. An output value is returned rather
+    /// than queued.
+    fn step(&mut self) -> Result<StepResult<Self::Word>, IntCodeError>;
+
+    fn pc(&self) -> usize;
+
+    fn push_output(&mut self, value: Self::Word);
+
+    fn pop_output(&mut self) -> Option<Self::Word>;
+
+    /// Runs until the machine halts, queuing any output. Fails if input is
+    /// needed but none is queued.
+    fn run(&mut self) -> Result<(), IntCodeError> {
+        match self.run_until_input_needed()? {
+            StepResult::NeedInput => Err(IntCodeError::InputExhausted { pc: self.pc() }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Runs until the next output value is produced. Returns `None` if the
+    /// machine halts first, and fails if it blocks on input.
+    fn run_until_output(&mut self) -> Result<Option<Self::Word>, IntCodeError> {
+        if let Some(value) = self.pop_output() {
+            return Ok(Some(value));
+        }
+
+        loop {
+            match self.step()? {
+                StepResult::Output(value) => return Ok(Some(value)),
+                StepResult::NeedInput => {
+                    return Err(IntCodeError::InputExhausted { pc: self.pc() })
+                }
+                StepResult::Halted => return Ok(None),
+                StepResult::Continue => {}
+            }
+        }
+    }
+
+    /// Runs until the machine halts or blocks on an empty input queue, and
+    /// returns which of the two happened. Outputs produced along the way are
+    /// queued.
+    fn run_until_input_needed(&mut self) -> Result<StepResult<Self::Word>, IntCodeError> {
+        loop {
+            match self.step()? {
+                StepResult::Output(value) => self.push_output(value),
+                StepResult::Continue => {}
+                result => return Ok(result),
+            }
+        }
+    }
+}
@@ -0,0 +1,423 @@
+use super::{
+    decode_with, Arithmetic, Engine, Instruction, IntCodeError, Memory, Parameter, ParameterMode,
+    StepResult,
+};
+use std::collections::VecDeque;
+
+// Addresses below this, where programs and their data live, are kept in a
+// flat vector, and anything above in a `Memory`. A vector this long takes up
+// about 2.5 MB with its cache.
+const FLAT_WORDS: usize = 1 << 16;
+
+// No instruction is longer than this, so a write can only change the
+// instructions starting at most this many words before it.
+const MAX_INSTRUCTION_SIZE: usize = 4;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Opcode {
+    Add,
+    Mul,
+    In,
+    Out,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustBase,
+    Halt,
+}
+
+/// An instruction decoded once and kept until its words are overwritten.
+#[derive(Debug, Copy, Clone)]
+struct Decoded {
+    opcode: Opcode,
+    modes: [ParameterMode; 3],
+    args: [i64; 3],
+}
+
+/// A faster Intcode engine for programs that are run many times over.
+///
+/// Low memory is a flat vector and every instruction there is decoded once,
+/// the first time it is executed. Writes drop the decoded instructions they overlap, so
+/// self-modifying programs behave exactly as on a `Machine`. There is no
+/// tracing, statistics or pluggable I/O: input comes from the queue and
+/// output goes to the queue.
+#[derive(Debug, Clone, Default)]
+pub struct FastMachine {
+    memory: Vec<i64>,
+    /// One past the highest address written, as in `Memory::len`.
+    len: usize,
+    /// Everything from `FLAT_WORDS` up.
+    high: Memory,
+    cache: Vec<Option<Decoded>>,
+    pc: usize,
+    relative_base: i64,
+    arithmetic: Arithmetic,
+    halted: bool,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+}
+
+impl FastMachine {
+    pub fn new<P: Into<Vec<i64>>>(program: P) -> Self {
+        let mut memory = program.into();
+        let mut high = Memory::new();
+        if memory.len() > FLAT_WORDS {
+            for (address, value) in (FLAT_WORDS..).zip(memory.drain(FLAT_WORDS..)) {
+                high.set(address, value);
+            }
+        }
+        FastMachine {
+            cache: vec![None; memory.len()],
+            len: memory.len().max(high.len()),
+            memory,
+            high,
+            ..FastMachine::default()
+        }
+    }
+
+    pub fn get(&self, address: usize) -> i64 {
+        match self.memory.get(address) {
+            Some(&value) => value,
+            None => self.high.get(address),
+        }
+    }
+
+    pub fn set(&mut self, address: usize, value: i64) {
+        self.len = self.len.max(address + 1);
+        if address >= FLAT_WORDS {
+            self.high.set(address, value);
+        } else {
+            if address >= self.memory.len() {
+                let len = (address + 1).max((self.memory.len() * 2).min(FLAT_WORDS));
+                self.memory.resize(len, 0);
+                self.cache.resize(len, None);
+            }
+            self.memory[address] = value;
+        }
+
+        // Instructions just below `FLAT_WORDS` may run on into `high`.
+        let start = address.saturating_sub(MAX_INSTRUCTION_SIZE - 1);
+        let end = (address + 1).min(self.cache.len());
+        if start < end {
+            for entry in &mut self.cache[start..end] {
+                *entry = None;
+            }
+        }
+    }
+
+    /// A copy of the memory as a `Machine` would see it.
+    pub fn to_memory(&self) -> Memory {
+        let mut memory = self.high.clone();
+        let flat = self.len.min(self.memory.len());
+        for (address, &value) in self.memory[..flat].iter().enumerate() {
+            memory.set(address, value);
+        }
+        memory
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    /// Sets what happens on overflow, `Arithmetic::Checked` by default.
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.arithmetic = arithmetic;
+    }
+
+    pub fn arithmetic(&self) -> Arithmetic {
+        self.arithmetic
+    }
+
+    /// Moves the program counter, e.g. to resume where compiled code
+    /// stopped.
+    pub fn set_pc(&mut self, pc: usize) {
+        self.pc = pc;
+    }
+
+    pub fn set_relative_base(&mut self, relative_base: i64) {
+        self.relative_base = relative_base;
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// Removes the next queued input value, as an input instruction would.
+    pub fn pop_input(&mut self) -> Option<i64> {
+        self.input.pop_front()
+    }
+
+    /// Queues an output value, as an output instruction would.
+    pub fn push_output(&mut self, value: i64) {
+        self.output.push_back(value);
+    }
+
+    pub fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop_front()
+    }
+
+    pub fn take_output(&mut self) -> Vec<i64> {
+        self.output.drain(..).collect()
+    }
+
+    /// Executes a single instruction, with the same results as
+    /// `Machine::step`.
+    pub fn step(&mut self) -> Result<StepResult, IntCodeError> {
+        if self.halted {
+            return Ok(StepResult::Halted);
+        }
+
+        let pc = self.pc;
+        let op = match self.cache.get(pc) {
+            Some(&Some(op)) => op,
+            _ => {
+                // Like `Machine`, wait for input before looking any closer.
+                if self.input.is_empty() && self.get(pc) % 100 == 3 {
+                    return Ok(StepResult::NeedInput);
+                }
+                let op = self.decode(pc)?;
+                if let Some(entry) = self.cache.get_mut(pc) {
+                    *entry = Some(op);
+                }
+                op
+            }
+        };
+
+        self.execute(&op).map_err(|fault| self.error(&op, fault))
+    }
+
+    /// Executes a decoded instruction. Failures are just a `Fault`, so the
+    /// hot path doesn't pass a whole `IntCodeError` around.
+    #[inline]
+    fn execute(&mut self, op: &Decoded) -> Result<StepResult, Fault> {
+        match op.opcode {
+            Opcode::Add => {
+                let value = self.arithmetic.add(self.read(op, 0)?, self.read(op, 1)?);
+                let value = value.ok_or(Fault::Overflow)?;
+                self.write(op, 2, value)?;
+                self.pc += 4;
+            }
+            Opcode::Mul => {
+                let value = self.arithmetic.mul(self.read(op, 0)?, self.read(op, 1)?);
+                let value = value.ok_or(Fault::Overflow)?;
+                self.write(op, 2, value)?;
+                self.pc += 4;
+            }
+            Opcode::In => {
+                if self.input.is_empty() {
+                    return Ok(StepResult::NeedInput);
+                }
+                let address = self.address(op, 0)?;
+                let value = self.input.pop_front().unwrap();
+                self.set(address, value);
+                self.pc += 2;
+            }
+            Opcode::Out => {
+                let value = self.read(op, 0)?;
+                self.pc += 2;
+                return Ok(StepResult::Output(value));
+            }
+            Opcode::JumpIfTrue => match self.read(op, 0)? {
+                0 => self.pc += 3,
+                _ => self.pc = self.jump_target(op)?,
+            },
+            Opcode::JumpIfFalse => match self.read(op, 0)? {
+                0 => self.pc = self.jump_target(op)?,
+                _ => self.pc += 3,
+            },
+            Opcode::LessThan => {
+                let value = (self.read(op, 0)? < self.read(op, 1)?) as i64;
+                self.write(op, 2, value)?;
+                self.pc += 4;
+            }
+            Opcode::Equals => {
+                let value = (self.read(op, 0)? == self.read(op, 1)?) as i64;
+                self.write(op, 2, value)?;
+                self.pc += 4;
+            }
+            Opcode::AdjustBase => {
+                let offset = self.read(op, 0)?;
+                self.relative_base = self
+                    .arithmetic
+                    .add(self.relative_base, offset)
+                    .ok_or(Fault::Overflow)?;
+                self.pc += 2;
+            }
+            Opcode::Halt => {
+                self.pc += 1;
+                self.halted = true;
+                return Ok(StepResult::Halted);
+            }
+        }
+
+        Ok(StepResult::Continue)
+    }
+
+    fn decode(&self, pc: usize) -> Result<Decoded, IntCodeError> {
+        decode_with(|address| self.get(address), pc).map(Decoded::from)
+    }
+
+    #[inline]
+    fn address(&self, op: &Decoded, n: usize) -> Result<usize, Fault> {
+        let address = match op.modes[n] {
+            ParameterMode::Relative => match self.relative_base.checked_add(op.args[n]) {
+                Some(address) => address,
+                None => {
+                    return Err(Fault::Address(
+                        self.relative_base.saturating_add(op.args[n]),
+                    ))
+                }
+            },
+            _ => op.args[n],
+        };
+        check(address)
+    }
+
+    #[inline]
+    fn read(&self, op: &Decoded, n: usize) -> Result<i64, Fault> {
+        match op.modes[n] {
+            ParameterMode::Immediate => Ok(op.args[n]),
+            _ => Ok(self.get(self.address(op, n)?)),
+        }
+    }
+
+    #[inline]
+    fn write(&mut self, op: &Decoded, n: usize, value: i64) -> Result<(), Fault> {
+        let address = self.address(op, n)?;
+        self.set(address, value);
+        Ok(())
+    }
+
+    fn jump_target(&self, op: &Decoded) -> Result<usize, Fault> {
+        check(self.read(op, 1)?)
+    }
+
+    #[cold]
+    fn error(&self, op: &Decoded, fault: Fault) -> IntCodeError {
+        let (pc, instruction) = (self.pc, op.instruction());
+        match fault {
+            Fault::Address(address) => IntCodeError::AddressOutOfRange {
+                pc,
+                instruction,
+                address,
+            },
+            Fault::Overflow => IntCodeError::Overflow { pc, instruction },
+        }
+    }
+}
+
+impl Engine for FastMachine {
+    type Word = i64;
+
+    fn step(&mut self) -> Result<StepResult, IntCodeError> {
+        FastMachine::step(self)
+    }
+
+    fn pc(&self) -> usize {
+        self.pc
+    }
+
+    fn push_output(&mut self, value: i64) {
+        self.output.push_back(value);
+    }
+
+    fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop_front()
+    }
+}
+
+/// Why an instruction failed, turned into an `IntCodeError` by `step`.
+enum Fault {
+    /// A negative address, or one past the end of `i64`.
+    Address(i64),
+    Overflow,
+}
+
+#[inline]
+fn check(address: i64) -> Result<usize, Fault> {
+    match address {
+        a if a >= 0 => Ok(a as usize),
+        _ => Err(Fault::Address(address)),
+    }
+}
+
+impl From<Instruction> for Decoded {
+    fn from(instruction: Instruction) -> Self {
+        let opcode = match instruction {
+            Instruction::Sum { .. } => Opcode::Add,
+            Instruction::Prod { .. } => Opcode::Mul,
+            Instruction::Input { .. } => Opcode::In,
+            Instruction::Output { .. } => Opcode::Out,
+            Instruction::JumpIfTrue { .. } => Opcode::JumpIfTrue,
+            Instruction::JumpIfFalse { .. } => Opcode::JumpIfFalse,
+            Instruction::LessThan { .. } => Opcode::LessThan,
+            Instruction::Equals { .. } => Opcode::Equals,
+            Instruction::RelativeBaseOffset { .. } => Opcode::AdjustBase,
+            Instruction::Halt => Opcode::Halt,
+        };
+
+        let mut decoded = Decoded {
+            opcode,
+            modes: [ParameterMode::Position; 3],
+            args: [0; 3],
+        };
+        for (n, param) in instruction.params().into_iter().enumerate() {
+            decoded.modes[n] = param.mode;
+            decoded.args[n] = param.value;
+        }
+        decoded
+    }
+}
+
+impl Decoded {
+    /// The instruction as `decode` would return it, for error reports.
+    fn instruction(&self) -> Instruction {
+        let param = |n: usize| Parameter {
+            value: self.args[n],
+            mode: self.modes[n],
+        };
+        match self.opcode {
+            Opcode::Add => Instruction::Sum {
+                i1: param(0),
+                i2: param(1),
+                out: param(2),
+            },
+            Opcode::Mul => Instruction::Prod {
+                i1: param(0),
+                i2: param(1),
+                out: param(2),
+            },
+            Opcode::In => Instruction::Input { out: param(0) },
+            Opcode::Out => Instruction::Output { i1: param(0) },
+            Opcode::JumpIfTrue => Instruction::JumpIfTrue {
+                i1: param(0),
+                pc: param(1),
+            },
+            Opcode::JumpIfFalse => Instruction::JumpIfFalse {
+                i1: param(0),
+                pc: param(1),
+            },
+            Opcode::LessThan => Instruction::LessThan {
+                i1: param(0),
+                i2: param(1),
+                out: param(2),
+            },
+            Opcode::Equals => Instruction::Equals {
+                i1: param(0),
+                i2: param(1),
+                out: param(2),
+            },
+            Opcode::AdjustBase => Instruction::RelativeBaseOffset { i1: param(0) },
+            Opcode::Halt => Instruction::Halt,
+        }
+    }
+}
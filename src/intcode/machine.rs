@@ -0,0 +1,457 @@
+use super::memory::{invalid_data, read_i64, read_u64, write_i64, write_u64};
+use super::{
+    err_input, process_instruction, process_instruction_traced, Arithmetic, Engine, FileTracer,
+    Input, IntCodeError, Memory, Output, Stats, TraceEvent, Tracer,
+};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const INPUT_OPCODE: i64 = 3;
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"ICVM";
+const SNAPSHOT_VERSION: u8 = 2;
+
+/// The state a machine is left in after executing one step.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StepResult<W = i64> {
+    /// An instruction was executed and the machine can keep running.
+    Continue,
+    /// An output instruction produced a value.
+    Output(W),
+    /// The next instruction reads input and the input queue is empty.
+    NeedInput,
+    /// The machine has halted.
+    Halted,
+}
+
+/// An Intcode VM that owns its memory, program counter and relative base.
+///
+/// Inputs are queued with `push_input` and outputs are collected until taken
+/// with `pop_output` or `take_output`, so a caller can pause a machine, inspect
+/// it and resume it later. Cloning is cheap: memory pages are shared until one
+/// of the clones writes to them.
+#[derive(Debug, Clone)]
+pub struct Machine {
+    memory: Memory,
+    pc: usize,
+    relative_base: i64,
+    arithmetic: Arithmetic,
+    halted: bool,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+    tracer: TracerSlot,
+    stats: Option<Stats>,
+}
+
+/// Holds the optional tracer. Clones of a machine start without one, since a
+/// tracer usually owns something like an open file.
+#[derive(Default)]
+struct TracerSlot(Option<Box<dyn Tracer + Send>>);
+
+impl Clone for TracerSlot {
+    fn clone(&self) -> Self {
+        TracerSlot(None)
+    }
+}
+
+impl fmt::Debug for TracerSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "Some(Tracer)"),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+impl Machine {
+    pub fn new<M: Into<Memory>>(program: M) -> Self {
+        Machine {
+            memory: program.into(),
+            pc: 0,
+            relative_base: 0,
+            arithmetic: Arithmetic::default(),
+            halted: false,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            tracer: TracerSlot::default(),
+            stats: None,
+        }
+    }
+
+    /// Sets what happens on overflow, `Arithmetic::Checked` by default.
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.arithmetic = arithmetic;
+    }
+
+    pub fn arithmetic(&self) -> Arithmetic {
+        self.arithmetic
+    }
+
+    /// Reports every instruction executed from now on to `tracer`.
+    pub fn set_tracer(&mut self, tracer: Box<dyn Tracer + Send>) {
+        self.tracer = TracerSlot(Some(tracer));
+    }
+
+    pub fn take_tracer(&mut self) -> Option<Box<dyn Tracer + Send>> {
+        self.tracer.0.take()
+    }
+
+    /// Collects execution statistics from now on, starting from `stats`.
+    pub fn set_stats(&mut self, stats: Stats) {
+        self.stats = Some(stats);
+    }
+
+    pub fn stats(&self) -> Option<&Stats> {
+        self.stats.as_ref()
+    }
+
+    pub fn take_stats(&mut self) -> Option<Stats> {
+        self.stats.take()
+    }
+
+    /// Attaches a `FileTracer` if `INTCODE_TRACE` is set and collects
+    /// statistics if `INTCODE_STATS` is set.
+    pub fn instrument_from_env(&mut self) {
+        if let Some(tracer) = FileTracer::from_env() {
+            self.set_tracer(Box::new(tracer));
+        }
+        if let Some(stats) = Stats::from_env() {
+            self.set_stats(stats);
+        }
+    }
+
+    pub fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    pub fn memory_mut(&mut self) -> &mut Memory {
+        &mut self.memory
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// Removes and returns the queued input that hasn't been read yet.
+    pub fn take_input(&mut self) -> Vec<i64> {
+        self.input.drain(..).collect()
+    }
+
+    pub fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop_front()
+    }
+
+    pub fn take_output(&mut self) -> Vec<i64> {
+        self.output.drain(..).collect()
+    }
+
+    /// Writes the complete state of the machine: memory, pc, relative base,
+    /// arithmetic and queued input and output. The tracer and statistics are
+    /// not saved.
+    pub fn save<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let arithmetic = match self.arithmetic {
+            Arithmetic::Checked => 0,
+            Arithmetic::Wrapping => 1,
+        };
+        w.write_all(SNAPSHOT_MAGIC)?;
+        w.write_all(&[SNAPSHOT_VERSION, self.halted as u8, arithmetic])?;
+        write_u64(w, self.pc as u64)?;
+        write_i64(w, self.relative_base)?;
+        for queue in &[&self.input, &self.output] {
+            write_u64(w, queue.len() as u64)?;
+            for &value in queue.iter() {
+                write_i64(w, value)?;
+            }
+        }
+        self.memory.write_to(w)
+    }
+
+    /// Restores a machine written by `save`.
+    pub fn load<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut header = [0; 7];
+        r.read_exact(&mut header)?;
+        if &header[..4] != SNAPSHOT_MAGIC {
+            return Err(invalid_data("Not an Intcode snapshot"));
+        }
+        if header[4] != SNAPSHOT_VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported snapshot version {}",
+                header[4]
+            )));
+        }
+
+        let halted = header[5] != 0;
+        let arithmetic = match header[6] {
+            0 => Arithmetic::Checked,
+            1 => Arithmetic::Wrapping,
+            other => return Err(invalid_data(format!("Unknown arithmetic {}", other))),
+        };
+        let pc = read_u64(r)? as usize;
+        let relative_base = read_i64(r)?;
+        let mut queues = Vec::new();
+        for _ in 0..2 {
+            let len = read_u64(r)?;
+            let queue = (0..len)
+                .map(|_| read_i64(r))
+                .collect::<io::Result<VecDeque<i64>>>()?;
+            queues.push(queue);
+        }
+        let output = queues.pop().unwrap();
+        let input = queues.pop().unwrap();
+
+        Ok(Machine {
+            memory: Memory::read_from(r)?,
+            pc,
+            relative_base,
+            arithmetic,
+            halted,
+            input,
+            output,
+            tracer: TracerSlot::default(),
+            stats: None,
+        })
+    }
+
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Machine::load(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// True when the next instruction reads input and the input queue is empty.
+    pub fn needs_input(&self) -> bool {
+        !self.halted && self.input.is_empty() && self.memory.get(self.pc) % 100 == INPUT_OPCODE
+    }
+
+    /// Executes a single instruction using the input queue.
+    ///
+    /// Instead of failing when the next instruction needs input and none is
+    /// queued, returns `StepResult::NeedInput` without executing it, so the
+    /// caller can `push_input` and step again. Output values are returned
+    /// directly rather than queued.
+    pub fn step(&mut self) -> Result<StepResult, IntCodeError> {
+        if self.halted {
+            return Ok(StepResult::Halted);
+        }
+
+        if self.needs_input() {
+            return Ok(StepResult::NeedInput);
+        }
+
+        let Machine {
+            memory,
+            pc,
+            relative_base,
+            arithmetic,
+            input,
+            tracer,
+            stats,
+            ..
+        } = self;
+
+        let mut out = None;
+        let running = execute(
+            memory,
+            pc,
+            relative_base,
+            *arithmetic,
+            Observers::new(tracer, stats),
+            &mut || match input.pop_front() {
+                Some(value) => Ok(value),
+                None => err_input(),
+            },
+            &mut |value| out = Some(value),
+        )?;
+
+        self.halted = !running;
+        Ok(match (running, out) {
+            (false, _) => StepResult::Halted,
+            (true, Some(value)) => StepResult::Output(value),
+            (true, None) => StepResult::Continue,
+        })
+    }
+
+    /// Executes a single instruction, reading input from and writing output to
+    /// the given callbacks instead of the queues.
+    pub fn step_with<FIn, FOut>(
+        &mut self,
+        input_fn: &mut FIn,
+        output_fn: &mut FOut,
+    ) -> Result<bool, IntCodeError>
+    where
+        FIn: FnMut() -> Result<i64, Box<dyn Error>>,
+        FOut: FnMut(i64),
+    {
+        if self.halted {
+            return Err(IntCodeError::Halted { pc: self.pc });
+        }
+
+        let running = execute(
+            &mut self.memory,
+            &mut self.pc,
+            &mut self.relative_base,
+            self.arithmetic,
+            Observers::new(&mut self.tracer, &mut self.stats),
+            input_fn,
+            output_fn,
+        )?;
+
+        self.halted = !running;
+        Ok(running)
+    }
+
+    /// Runs until the machine halts, using the given callbacks for I/O.
+    pub fn run_with<FIn, FOut>(
+        &mut self,
+        input_fn: &mut FIn,
+        output_fn: &mut FOut,
+    ) -> Result<(), IntCodeError>
+    where
+        FIn: FnMut() -> Result<i64, Box<dyn Error>>,
+        FOut: FnMut(i64),
+    {
+        while !self.halted {
+            self.step_with(input_fn, output_fn)?;
+        }
+        Ok(())
+    }
+
+    /// Executes a single instruction, reading from `input` and writing to
+    /// `output` instead of the queues.
+    pub fn step_io<I, O>(&mut self, input: &mut I, output: &mut O) -> Result<bool, IntCodeError>
+    where
+        I: Input + ?Sized,
+        O: Output + ?Sized,
+    {
+        let pc = self.pc;
+        let mut failed = None;
+        let running = self.step_with(&mut || input.read(), &mut |value| {
+            if let Err(err) = output.write(value) {
+                failed = Some(err);
+            }
+        })?;
+
+        match failed {
+            Some(err) => Err(IntCodeError::Output {
+                pc,
+                details: err.to_string(),
+            }),
+            None => Ok(running),
+        }
+    }
+
+    /// Runs until the machine halts, reading from `input` and writing to
+    /// `output`.
+    pub fn run_io<I, O>(&mut self, input: &mut I, output: &mut O) -> Result<(), IntCodeError>
+    where
+        I: Input + ?Sized,
+        O: Output + ?Sized,
+    {
+        while !self.halted {
+            self.step_io(input, output)?;
+        }
+        Ok(())
+    }
+}
+
+impl Engine for Machine {
+    type Word = i64;
+
+    fn step(&mut self) -> Result<StepResult, IntCodeError> {
+        Machine::step(self)
+    }
+
+    fn pc(&self) -> usize {
+        self.pc
+    }
+
+    fn push_output(&mut self, value: i64) {
+        self.output.push_back(value);
+    }
+
+    fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop_front()
+    }
+}
+
+/// Forwards trace events to the tracer and the statistics, whichever are set.
+struct Observers<'a> {
+    tracer: Option<&'a mut dyn Tracer>,
+    stats: Option<&'a mut Stats>,
+}
+
+impl<'a> Observers<'a> {
+    fn new(tracer: &'a mut TracerSlot, stats: &'a mut Option<Stats>) -> Self {
+        Observers {
+            tracer: match tracer.0 {
+                Some(ref mut tracer) => Some(tracer.as_mut()),
+                None => None,
+            },
+            stats: stats.as_mut(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tracer.is_none() && self.stats.is_none()
+    }
+}
+
+impl<'a> Tracer for Observers<'a> {
+    fn trace(&mut self, event: &TraceEvent) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.trace(event);
+        }
+        if let Some(stats) = self.stats.as_mut() {
+            stats.record(event);
+        }
+    }
+}
+
+fn execute<FIn, FOut>(
+    memory: &mut Memory,
+    pc: &mut usize,
+    relative_base: &mut i64,
+    arithmetic: Arithmetic,
+    mut observers: Observers,
+    input_fn: &mut FIn,
+    output_fn: &mut FOut,
+) -> Result<bool, IntCodeError>
+where
+    FIn: FnMut() -> Result<i64, Box<dyn Error>>,
+    FOut: FnMut(i64),
+{
+    if observers.is_empty() {
+        return process_instruction(memory, pc, relative_base, arithmetic, input_fn, output_fn);
+    }
+
+    process_instruction_traced(
+        memory,
+        pc,
+        relative_base,
+        arithmetic,
+        input_fn,
+        output_fn,
+        &mut observers,
+    )
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::io;
+use std::io::{Read, Write};
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut};
+use std::sync::Arc;
+
+const PAGE_BITS: usize = 10;
+const PAGE_SIZE: usize = 1 << PAGE_BITS;
+
+// Addresses above this are kept in a sparse map instead of pages, so a stray
+// write far past the program doesn't allocate a huge page table.
+const MAX_PAGED_ADDRESS: usize = 1 << 24;
+
+static ZERO: i64 = 0;
+
+// Pages are shared between clones and copied on the first write, which keeps
+// cloning a machine cheap.
+type Page<T> = Arc<[T; PAGE_SIZE]>;
+
+/// Values by address, stored in fixed-size pages which are only allocated
+/// when written to, with very high addresses in a sparse map instead. This
+/// backs `Memory` as well as the other engines' memories and caches.
+#[derive(Debug, Clone)]
+pub(crate) struct Pages<T> {
+    pages: Vec<Option<Page<T>>>,
+    sparse: HashMap<usize, T>,
+    len: usize,
+}
+
+impl<T> Default for Pages<T> {
+    fn default() -> Self {
+        Pages {
+            pages: Vec::new(),
+            sparse: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T: Clone + Default> Pages<T> {
+    /// The value at `address`, or `None` if it has never been written.
+    #[inline]
+    pub fn get(&self, address: usize) -> Option<&T> {
+        if address >= MAX_PAGED_ADDRESS {
+            return self.sparse.get(&address);
+        }
+        match self.pages.get(address >> PAGE_BITS) {
+            Some(Some(page)) => Some(&page[address & (PAGE_SIZE - 1)]),
+            _ => None,
+        }
+    }
+
+    /// The value at `address`, allocating it if necessary.
+    pub fn get_mut(&mut self, address: usize) -> &mut T {
+        if address >= self.len {
+            self.len = address + 1;
+        }
+
+        if address >= MAX_PAGED_ADDRESS {
+            return self.sparse.entry(address).or_default();
+        }
+
+        let index = address >> PAGE_BITS;
+        if index >= self.pages.len() {
+            self.pages.resize_with(index + 1, || None);
+        }
+
+        let page = self.pages[index].get_or_insert_with(|| Arc::new(new_page()));
+        &mut Arc::make_mut(page)[address & (PAGE_SIZE - 1)]
+    }
+
+    /// One past the highest address that has been written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Like `len`, but leaving out the sparse map: once anything has been
+    /// written there, this is the end of the last allocated page.
+    pub fn paged_len(&self) -> usize {
+        match self.len <= MAX_PAGED_ADDRESS {
+            true => self.len,
+            false => self.pages.len() << PAGE_BITS,
+        }
+    }
+
+    /// Every allocated page with its index.
+    fn pages(&self) -> impl Iterator<Item = (usize, &[T; PAGE_SIZE])> {
+        self.pages
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| p.as_ref().map(|p| (i, &**p)))
+    }
+}
+
+fn new_page<T: Default>() -> [T; PAGE_SIZE] {
+    std::array::from_fn(|_| T::default())
+}
+
+/// Intcode memory that grows on demand.
+///
+/// Low addresses are stored in fixed-size pages which are only allocated when
+/// written to. Very high addresses fall back to a sparse map. Reading an
+/// address that has never been written returns 0.
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    words: Pages<i64>,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Memory::default()
+    }
+
+    #[inline]
+    pub fn get(&self, address: usize) -> i64 {
+        *self.get_ref(address)
+    }
+
+    pub fn set(&mut self, address: usize, value: i64) {
+        *self.get_mut(address) = value;
+    }
+
+    /// One past the highest address that has been written.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of pages and sparse cells currently allocated.
+    pub fn allocated_pages(&self) -> usize {
+        self.words.pages().count()
+    }
+
+    pub fn sparse_cells(&self) -> usize {
+        self.words.sparse.len()
+    }
+
+    /// Copies the words in `[0, len)` into a vector. Words in the sparse map
+    /// are left out, so a single far write doesn't make the copy huge; the
+    /// copy then ends with the last allocated page.
+    pub fn to_vec(&self) -> Vec<i64> {
+        (0..self.words.paged_len()).map(|a| self.get(a)).collect()
+    }
+
+    /// Writes the memory in a compact little-endian format: the length, then
+    /// every allocated page with its index, then every sparse cell.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write_u64(w, self.len() as u64)?;
+
+        let pages: Vec<(usize, &[i64; PAGE_SIZE])> = self
+            .words
+            .pages()
+            .filter(|(_, p)| p.iter().any(|&v| v != 0))
+            .collect();
+        write_u64(w, pages.len() as u64)?;
+        for (index, page) in pages {
+            write_u64(w, index as u64)?;
+            for &value in page.iter() {
+                write_i64(w, value)?;
+            }
+        }
+
+        write_u64(w, self.words.sparse.len() as u64)?;
+        for (&address, &value) in self.words.sparse.iter() {
+            write_u64(w, address as u64)?;
+            write_i64(w, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads memory written by `write_to`.
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut words = Pages::default();
+        let len = read_u64(r)? as usize;
+
+        for _ in 0..read_u64(r)? {
+            let index = read_u64(r)? as usize;
+            if index >= MAX_PAGED_ADDRESS >> PAGE_BITS {
+                return Err(invalid_data(format!("Page index {} out of range", index)));
+            }
+            let mut page = [0; PAGE_SIZE];
+            for value in page.iter_mut() {
+                *value = read_i64(r)?;
+            }
+            if index >= words.pages.len() {
+                words.pages.resize_with(index + 1, || None);
+            }
+            words.pages[index] = Some(Arc::new(page));
+        }
+
+        for _ in 0..read_u64(r)? {
+            let address = read_u64(r)? as usize;
+            let value = read_i64(r)?;
+            words.sparse.insert(address, value);
+        }
+
+        words.len = len;
+        Ok(Memory { words })
+    }
+
+    #[inline]
+    fn get_ref(&self, address: usize) -> &i64 {
+        self.words.get(address).unwrap_or(&ZERO)
+    }
+
+    fn get_mut(&mut self, address: usize) -> &mut i64 {
+        self.words.get_mut(address)
+    }
+}
+
+impl Index<usize> for Memory {
+    type Output = i64;
+
+    fn index(&self, address: usize) -> &i64 {
+        self.get_ref(address)
+    }
+}
+
+impl IndexMut<usize> for Memory {
+    fn index_mut(&mut self, address: usize) -> &mut i64 {
+        self.get_mut(address)
+    }
+}
+
+impl From<Vec<i64>> for Memory {
+    fn from(program: Vec<i64>) -> Self {
+        program.into_iter().collect()
+    }
+}
+
+impl From<&[i64]> for Memory {
+    fn from(program: &[i64]) -> Self {
+        program.iter().cloned().collect()
+    }
+}
+
+impl FromIterator<i64> for Memory {
+    fn from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Self {
+        let mut memory = Memory::new();
+        for (address, value) in iter.into_iter().enumerate() {
+            memory.set(address, value);
+        }
+        memory
+    }
+}
+
+pub(crate) fn write_u64<W: Write>(w: &mut W, value: u64) -> io::Result<()> {
+    w.write_all(&value.to_le_bytes())
+}
+
+pub(crate) fn write_i64<W: Write>(w: &mut W, value: i64) -> io::Result<()> {
+    w.write_all(&value.to_le_bytes())
+}
+
+pub(crate) fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    r.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+pub(crate) fn read_i64<R: Read>(r: &mut R) -> io::Result<i64> {
+    let mut bytes = [0; 8];
+    r.read_exact(&mut bytes)?;
+    Ok(i64::from_le_bytes(bytes))
+}
+
+pub(crate) fn invalid_data<T: AsRef<str>>(msg: T) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.as_ref())
+}
@@ -0,0 +1,261 @@
+//! Machines wired together so the output of one is the input of others.
+//!
+//! ```text
+//! let mut network = Network::ring(amplifiers);
+//! network.push_input(network.first(), 0);
+//! network.run()?;
+//! let signal = network.last_output(network.last());
+//! ```
+
+use super::{Engine, IntCodeError, Machine, Memory};
+use crossbeam_channel::Sender;
+use std::collections::HashMap;
+use std::mem;
+use std::thread;
+
+/// Identifies a machine in a `Network`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    name: String,
+    machine: Machine,
+    /// Nodes that receive a copy of every output value.
+    targets: Vec<NodeId>,
+    /// Every value the node has output.
+    outputs: Vec<i64>,
+}
+
+/// A set of named machines and the links between them.
+///
+/// Values a machine outputs are queued as input for every node it is
+/// connected to, and all outputs are recorded so they can be read once the
+/// network has run. Initial inputs, like phase settings, are seeded with
+/// `push_input` or queued on the machine before adding it.
+#[derive(Debug, Clone, Default)]
+pub struct Network {
+    nodes: Vec<Node>,
+    names: HashMap<String, NodeId>,
+}
+
+impl Network {
+    pub fn new() -> Self {
+        Network::default()
+    }
+
+    /// Connects the machines one after the other, named by their position.
+    pub fn chain<I: IntoIterator<Item = Machine>>(machines: I) -> Self {
+        let mut network = Network::new();
+        let mut previous = None;
+        for (i, machine) in machines.into_iter().enumerate() {
+            let node = network.add(i.to_string(), machine);
+            if let Some(previous) = previous {
+                network.connect(previous, node);
+            }
+            previous = Some(node);
+        }
+        network
+    }
+
+    /// A chain whose last machine feeds back into the first.
+    pub fn ring<I: IntoIterator<Item = Machine>>(machines: I) -> Self {
+        let mut network = Network::chain(machines);
+        if !network.nodes.is_empty() {
+            network.connect(network.last(), network.first());
+        }
+        network
+    }
+
+    /// Adds a machine. If the name is already taken, `node` finds the new
+    /// machine from now on.
+    pub fn add<S: Into<String>>(&mut self, name: S, machine: Machine) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        let name = name.into();
+        self.names.insert(name.clone(), id);
+        self.nodes.push(Node {
+            name,
+            machine,
+            targets: Vec::new(),
+            outputs: Vec::new(),
+        });
+        id
+    }
+
+    /// Sends the outputs of `from` to `to` as well.
+    pub fn connect(&mut self, from: NodeId, to: NodeId) {
+        self.nodes[from.0].targets.push(to);
+    }
+
+    pub fn node(&self, name: &str) -> Option<NodeId> {
+        self.names.get(name).cloned()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.nodes[id.0].name
+    }
+
+    /// The node added first. Panics if the network is empty.
+    pub fn first(&self) -> NodeId {
+        assert!(!self.nodes.is_empty(), "the network is empty");
+        NodeId(0)
+    }
+
+    /// The node added last. Panics if the network is empty.
+    pub fn last(&self) -> NodeId {
+        assert!(!self.nodes.is_empty(), "the network is empty");
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Every node, in the order they were added.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn machine(&self, id: NodeId) -> &Machine {
+        &self.nodes[id.0].machine
+    }
+
+    pub fn machine_mut(&mut self, id: NodeId) -> &mut Machine {
+        &mut self.nodes[id.0].machine
+    }
+
+    pub fn push_input(&mut self, id: NodeId, value: i64) {
+        self.nodes[id.0].machine.push_input(value);
+    }
+
+    /// Every value the node has output so far.
+    pub fn outputs(&self, id: NodeId) -> &[i64] {
+        &self.nodes[id.0].outputs
+    }
+
+    pub fn last_output(&self, id: NodeId) -> Option<i64> {
+        self.nodes[id.0].outputs.last().cloned()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.nodes.iter().all(|node| node.machine.is_halted())
+    }
+
+    /// Runs the machines in turn on this thread, each until it needs input
+    /// nobody has sent yet, until all of them have halted.
+    ///
+    /// Fails when a machine fails, or with `InputExhausted` when the machines
+    /// still running are all waiting for input.
+    pub fn run(&mut self) -> Result<(), IntCodeError> {
+        loop {
+            let mut progress = false;
+
+            for i in 0..self.nodes.len() {
+                let node = &mut self.nodes[i];
+                node.machine.run_until_input_needed()?;
+
+                let values = node.machine.take_output();
+                if values.is_empty() {
+                    continue;
+                }
+                progress = true;
+                node.outputs.extend_from_slice(&values);
+
+                let targets = node.targets.clone();
+                for target in targets {
+                    let machine = &mut self.nodes[target.0].machine;
+                    for &value in &values {
+                        machine.push_input(value);
+                    }
+                }
+            }
+
+            if self.is_halted() {
+                return Ok(());
+            }
+            if !progress {
+                let blocked = self.nodes.iter().find(|node| !node.machine.is_halted());
+                let pc = blocked.map_or(0, |node| node.machine.pc());
+                return Err(IntCodeError::InputExhausted { pc });
+            }
+        }
+    }
+
+    /// Runs every machine on its own thread, connected by channels, until all
+    /// of them have halted. Returns the first error by node order.
+    ///
+    /// A machine whose input can never arrive, because every node sending to
+    /// it has halted, fails with `InputExhausted`. Unlike `run`, a network
+    /// whose machines all wait for each other blocks forever.
+    pub fn run_threaded(&mut self) -> Result<(), IntCodeError> {
+        let (senders, receivers): (Vec<_>, Vec<_>) = self
+            .nodes
+            .iter()
+            .map(|_| crossbeam_channel::unbounded::<i64>())
+            .unzip();
+
+        // Queue every machine's input before any starts, so none can receive
+        // a value from another ahead of its own.
+        for (node, seed) in self.nodes.iter_mut().zip(&senders) {
+            for value in node.machine.take_input() {
+                seed.send(value).unwrap();
+            }
+        }
+
+        let mut handles = Vec::new();
+        for (node, mut input) in self.nodes.iter_mut().zip(receivers) {
+            let mut machine = mem::replace(&mut node.machine, Machine::new(Memory::new()));
+            let targets: Vec<Sender<i64>> = node
+                .targets
+                .iter()
+                .map(|target| senders[target.0].clone())
+                .collect();
+
+            handles.push(thread::spawn(move || {
+                let mut outputs = Vec::new();
+                let result = machine.run_io(&mut input, &mut |value| {
+                    outputs.push(value);
+                    // A halted target has dropped its receiver; like a halted
+                    // machine in `run`, it just never reads the value.
+                    for target in &targets {
+                        let _ = target.send(value);
+                    }
+                });
+                (machine, outputs, result)
+            }));
+        }
+        drop(senders);
+
+        let mut result = Ok(());
+        for (node, handle) in self.nodes.iter_mut().zip(handles) {
+            match handle.join() {
+                Ok((machine, outputs, node_result)) => {
+                    node.machine = machine;
+                    node.outputs.extend(outputs);
+                    if result.is_ok() {
+                        result = node_result;
+                    }
+                }
+                Err(_) => {
+                    if result.is_ok() {
+                        result = Err(IntCodeError::new(format!(
+                            "Machine '{}' panicked",
+                            node.name
+                        )));
+                    }
+                }
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,199 @@
+use crate::parser::{InputError, ParseError, Source};
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str;
+
+const WORD_SIZE: usize = 8;
+
+/// Optionally starts a binary program, so it can't be mistaken for text.
+/// Binary programs without it are read from `.bin` files or with `--binary`.
+pub const BINARY_MAGIC: &[u8; WORD_SIZE] = b"INTCODE\x01";
+
+/// Given before the input, reads it as a binary program even without
+/// `BINARY_MAGIC`.
+pub const BINARY_FLAG: &str = "--binary";
+
+/// Why a program could not be loaded.
+#[derive(Debug)]
+pub enum LoadError {
+    Input(InputError),
+    /// A text program contains something that isn't a number.
+    Parse(ParseError),
+    /// A binary program, after any `BINARY_MAGIC`, doesn't consist of whole
+    /// words.
+    Binary {
+        len: usize,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Input(err) => write!(f, "{}", err),
+            LoadError::Parse(err) => write!(f, "Invalid program: {}", err),
+            LoadError::Binary { len } => write!(
+                f,
+                "Invalid binary program: {} bytes of words is not a multiple of {}",
+                len, WORD_SIZE
+            ),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Input(err) => Some(err),
+            LoadError::Parse(err) => Some(err),
+            LoadError::Binary { .. } => None,
+        }
+    }
+}
+
+impl From<InputError> for LoadError {
+    fn from(err: InputError) -> Self {
+        LoadError::Input(err)
+    }
+}
+
+impl From<ParseError> for LoadError {
+    fn from(err: ParseError) -> Self {
+        LoadError::Parse(err)
+    }
+}
+
+/// Parses a program written as text. Words are separated by commas,
+/// whitespace or both, so comma separated lists as well as one word per line
+/// work, and everything from a `#` to the end of the line is a comment.
+pub fn parse_program(input: &str) -> Result<Vec<i64>, ParseError> {
+    let mut program = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let code = match line.find('#') {
+            Some(i) => &line[..i],
+            None => line,
+        };
+
+        let mut word = String::new();
+        let mut start = 0;
+        for (column, c) in code.chars().chain(Some(' ')).enumerate() {
+            if c == ',' || c.is_whitespace() {
+                if !word.is_empty() {
+                    program.push(parse_word(&word, index, start)?);
+                    word.clear();
+                }
+            } else {
+                if word.is_empty() {
+                    start = column + 1;
+                }
+                word.push(c);
+            }
+        }
+    }
+
+    Ok(program)
+}
+
+fn parse_word(word: &str, index: usize, column: usize) -> Result<i64, ParseError> {
+    word.parse().map_err(|err| ParseError {
+        line: index + 1,
+        column,
+        message: format!("invalid value '{}': {}", word, err),
+    })
+}
+
+/// Decodes a program in either format: binary if it starts with
+/// `BINARY_MAGIC`, and text as read by `parse_program` otherwise.
+pub fn decode_program(bytes: &[u8]) -> Result<Vec<i64>, LoadError> {
+    if bytes.starts_with(&BINARY_MAGIC[..]) {
+        return decode_binary(bytes);
+    }
+
+    match str::from_utf8(bytes) {
+        Ok(text) => Ok(parse_program(text)?),
+        Err(err) => {
+            let valid = &bytes[..err.valid_up_to()];
+            let line = valid.split(|&b| b == b'\n').next_back().unwrap_or(&[]);
+            Err(LoadError::Parse(ParseError {
+                line: valid.iter().filter(|&&b| b == b'\n').count() + 1,
+                column: String::from_utf8_lossy(line).chars().count() + 1,
+                message: "invalid UTF-8 in a text program".to_string(),
+            }))
+        }
+    }
+}
+
+/// Decodes a binary program: one little-endian `i64` per word, after an
+/// optional `BINARY_MAGIC`.
+pub fn decode_binary(bytes: &[u8]) -> Result<Vec<i64>, LoadError> {
+    let words = bytes.strip_prefix(&BINARY_MAGIC[..]).unwrap_or(bytes);
+    if !words.len().is_multiple_of(WORD_SIZE) {
+        return Err(LoadError::Binary { len: words.len() });
+    }
+    Ok(words
+        .chunks(WORD_SIZE)
+        .map(|chunk| {
+            let mut word = [0; WORD_SIZE];
+            word.copy_from_slice(chunk);
+            i64::from_le_bytes(word)
+        })
+        .collect())
+}
+
+/// Encodes a program in the binary format read by `decode_program`.
+pub fn encode_program(program: &[i64]) -> Vec<u8> {
+    let words = program.iter().flat_map(|word| word.to_le_bytes());
+    BINARY_MAGIC.iter().cloned().chain(words).collect()
+}
+
+/// Reads a program in either format from `source`. Files ending in `.bin`
+/// are always binary.
+pub fn read_program(source: &Source) -> Result<Vec<i64>, LoadError> {
+    let bytes = source.read_bytes()?;
+    match source {
+        Source::File(path) if path.extension().is_some_and(|e| e == "bin") => decode_binary(&bytes),
+        _ => decode_program(&bytes),
+    }
+}
+
+/// Reads a program in either format from a file.
+pub fn load_program<P: AsRef<Path>>(path: P) -> Result<Vec<i64>, LoadError> {
+    read_program(&Source::File(PathBuf::from(path.as_ref())))
+}
+
+/// Reads the program named on the command line, as picked by
+/// `Source::from_args`, and returns it with the arguments following it.
+/// `--binary` before the input reads it as binary.
+pub fn program_from_args(
+    args: &[String],
+    day: Option<u32>,
+) -> Result<(Vec<i64>, &[String]), LoadError> {
+    match args.get(1) {
+        // `from_args` skips the first argument, which is then the flag.
+        Some(flag) if flag == BINARY_FLAG => {
+            let (source, extra) = Source::from_args(&args[1..], day)?;
+            Ok((decode_binary(&source.read_bytes()?)?, extra))
+        }
+        _ => {
+            let (source, extra) = Source::from_args(args, day)?;
+            Ok((read_program(&source)?, extra))
+        }
+    }
+}
+
+/// `program_from_args` for binaries: on failure, prints the error and exits
+/// with status 2 for bad arguments and 1 otherwise.
+pub fn program_from_args_or_exit(args: &[String], day: Option<u32>) -> (Vec<i64>, &[String]) {
+    match program_from_args(args, day) {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(match err {
+                LoadError::Input(InputError::Usage(_)) => 2,
+                _ => 1,
+            });
+        }
+    }
+}
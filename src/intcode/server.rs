@@ -0,0 +1,143 @@
+//! Serving an Intcode machine over TCP or Unix sockets, one machine per
+//! connection.
+//!
+//! The protocol is line-based: the client sends one input word per line
+//! and the server answers with one output word per line. Blank lines are
+//! ignored. When the machine halts the server closes the connection; when it
+//! fails the server sends a last line, `error: ` and the reason, first.
+
+use super::{err_input, Input, IntCodeError, Machine, Output};
+use std::error::Error;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::thread;
+use std::time::Duration;
+
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Runs a copy of a machine for every client that connects.
+#[derive(Debug, Clone)]
+pub struct Server {
+    machine: Machine,
+}
+
+impl Server {
+    pub fn new(machine: Machine) -> Self {
+        Server { machine }
+    }
+
+    /// Accepts connections forever, each served on its own thread.
+    pub fn serve_tcp(&self, listener: &TcpListener) {
+        self.serve(listener.incoming(), Server::handle_tcp, |stream| {
+            stream.peer_addr().map(|address| address.to_string())
+        })
+    }
+
+    /// Accepts connections forever, each served on its own thread.
+    #[cfg(unix)]
+    pub fn serve_unix(&self, listener: &UnixListener) {
+        self.serve(listener.incoming(), Server::handle_unix, |stream| {
+            stream.peer_addr().map(|address| format!("{:?}", address))
+        })
+    }
+
+    /// Failed accepts, e.g. when out of file descriptors, and failed
+    /// connections are logged to stderr; neither stops the server.
+    fn serve<S, I>(
+        &self,
+        incoming: I,
+        handle: fn(&Server, S) -> Result<(), IntCodeError>,
+        peer: fn(&S) -> io::Result<String>,
+    ) where
+        S: Send + 'static,
+        I: Iterator<Item = io::Result<S>>,
+    {
+        for stream in incoming {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("Accepting a connection failed: {}", err);
+                    // Give whatever ran out a moment to recover.
+                    thread::sleep(ACCEPT_BACKOFF);
+                    continue;
+                }
+            };
+            let peer = peer(&stream).unwrap_or_else(|err| format!("unknown peer ({})", err));
+            let server = self.clone();
+            thread::spawn(move || {
+                if let Err(err) = handle(&server, stream) {
+                    eprintln!("{}: {}", peer, err);
+                }
+            });
+        }
+    }
+
+    pub fn handle_tcp(&self, stream: TcpStream) -> Result<(), IntCodeError> {
+        let reader = stream.try_clone().map_err(connection_error)?;
+        self.handle(BufReader::new(reader), stream)
+    }
+
+    #[cfg(unix)]
+    pub fn handle_unix(&self, stream: UnixStream) -> Result<(), IntCodeError> {
+        let reader = stream.try_clone().map_err(connection_error)?;
+        self.handle(BufReader::new(reader), stream)
+    }
+
+    /// Runs a fresh copy of the machine, reading input words from `reader`
+    /// and writing outputs to `writer`, until it halts or fails.
+    ///
+    /// A client that disconnects while the machine waits for input ends it
+    /// with `InputExhausted`; one that stops reading ends it with an output
+    /// error.
+    pub fn handle<R: BufRead, W: Write>(&self, reader: R, writer: W) -> Result<(), IntCodeError> {
+        let mut output = WordOutput(writer);
+        let result = self
+            .machine
+            .clone()
+            .run_io(&mut WordInput(reader), &mut output);
+
+        // The client may well be gone already, so this is best effort.
+        if let Err(ref err) = result {
+            let writer = &mut output.0;
+            let _ = writeln!(writer, "error: {}", err);
+            let _ = writer.flush();
+        }
+        result
+    }
+}
+
+/// Reads one word per line, skipping blank lines.
+struct WordInput<R>(R);
+
+impl<R: BufRead> Input for WordInput<R> {
+    fn read(&mut self) -> Result<i64, Box<dyn Error>> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.0.read_line(&mut line)? == 0 {
+                return err_input();
+            }
+            let word = line.trim();
+            if !word.is_empty() {
+                return Ok(word.parse()?);
+            }
+        }
+    }
+}
+
+/// Writes one word per line, flushing each so the client sees it at once.
+struct WordOutput<W>(W);
+
+impl<W: Write> Output for WordOutput<W> {
+    fn write(&mut self, value: i64) -> Result<(), Box<dyn Error>> {
+        writeln!(self.0, "{}", value)?;
+        Ok(self.0.flush()?)
+    }
+}
+
+fn connection_error(err: io::Error) -> IntCodeError {
+    IntCodeError::new(format!("Connection failed: {}", err))
+}
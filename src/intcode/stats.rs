@@ -0,0 +1,134 @@
+use super::{Instruction, ParameterMode, TraceEvent, Tracer};
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+
+/// Environment variable that makes `Stats::from_env` enable statistics.
+pub const STATS_ENV: &str = "INTCODE_STATS";
+
+/// Number of addresses listed in the hotspot section of the summary.
+const HOTSPOTS: usize = 10;
+
+/// Execution statistics collected from the instructions a machine executes.
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    pub instructions: u64,
+    pub inputs: u64,
+    pub outputs: u64,
+    /// Highest address read, written or fetched from.
+    pub peak_address: usize,
+    pub opcodes: HashMap<&'static str, u64>,
+    /// Number of times the instruction at each address was executed.
+    pub hits: HashMap<usize, u64>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats::default()
+    }
+
+    /// Returns empty statistics if `INTCODE_STATS` is set.
+    pub fn from_env() -> Option<Self> {
+        env::var_os(STATS_ENV).map(|_| Stats::new())
+    }
+
+    /// Adds the counts of `other`, e.g. to sum up several runs of a program.
+    pub fn merge(&mut self, other: &Stats) {
+        self.instructions += other.instructions;
+        self.inputs += other.inputs;
+        self.outputs += other.outputs;
+        self.peak_address = self.peak_address.max(other.peak_address);
+        for (&mnemonic, &count) in &other.opcodes {
+            *self.opcodes.entry(mnemonic).or_insert(0) += count;
+        }
+        for (&address, &count) in &other.hits {
+            *self.hits.entry(address).or_insert(0) += count;
+        }
+    }
+
+    pub fn record(&mut self, event: &TraceEvent) {
+        let instruction = &event.instruction;
+
+        self.instructions += 1;
+        *self.opcodes.entry(instruction.mnemonic()).or_insert(0) += 1;
+        *self.hits.entry(event.pc).or_insert(0) += 1;
+
+        match instruction {
+            Instruction::Input { .. } => self.inputs += 1,
+            Instruction::Output { .. } => self.outputs += 1,
+            _ => {}
+        }
+
+        let touched = instruction
+            .params()
+            .into_iter()
+            .filter_map(|p| match p.mode {
+                ParameterMode::Position => Some(p.value),
+                ParameterMode::Relative => Some(event.relative_base + p.value),
+                ParameterMode::Immediate => None,
+            })
+            .filter(|&a| a >= 0)
+            .map(|a| a as usize)
+            .chain(Some(event.pc + instruction.size() - 1));
+        for address in touched {
+            self.peak_address = self.peak_address.max(address);
+        }
+    }
+
+    /// The most executed addresses, most executed first.
+    pub fn hotspots(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut hits: Vec<(usize, u64)> = self.hits.iter().map(|(&a, &c)| (a, c)).collect();
+        hits.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        hits.truncate(n);
+        hits
+    }
+}
+
+impl Tracer for Stats {
+    fn trace(&mut self, event: &TraceEvent) {
+        self.record(event);
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Instructions executed: {}", self.instructions)?;
+        writeln!(f, "Inputs: {}, outputs: {}", self.inputs, self.outputs)?;
+        writeln!(f, "Peak address: {}", self.peak_address)?;
+
+        let mut opcodes: Vec<(&str, u64)> = self.opcodes.iter().map(|(&m, &c)| (m, c)).collect();
+        opcodes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        writeln!(f)?;
+        writeln!(f, "{:<8}{:>12}{:>8}", "Opcode", "Count", "%")?;
+        for (mnemonic, count) in opcodes {
+            writeln!(
+                f,
+                "{:<8}{:>12}{:>8.1}",
+                mnemonic,
+                count,
+                percent(count, self.instructions)
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "{:<8}{:>12}{:>8}", "Address", "Hits", "%")?;
+        for (address, count) in self.hotspots(HOTSPOTS) {
+            writeln!(
+                f,
+                "{:<8}{:>12}{:>8.1}",
+                address,
+                count,
+                percent(count, self.instructions)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn percent(count: u64, total: u64) -> f64 {
+    match total {
+        0 => 0.0,
+        _ => count as f64 * 100.0 / total as f64,
+    }
+}
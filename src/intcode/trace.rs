@@ -0,0 +1,80 @@
+use super::Instruction;
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+
+/// Environment variable naming the file `FileTracer::from_env` writes to.
+pub const TRACE_ENV: &str = "INTCODE_TRACE";
+
+/// Everything one executed instruction did.
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    pub pc: usize,
+    pub relative_base: i64,
+    pub instruction: Instruction,
+    /// Resolved values of the parameters the instruction reads, in order.
+    pub operands: Vec<i64>,
+    /// Address and new value of the memory cell written, if any.
+    pub write: Option<(usize, i64)>,
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operands: Vec<String> = self.operands.iter().map(i64::to_string).collect();
+        write!(
+            f,
+            "{:>6} rb={:<6} {:<32} ({})",
+            self.pc,
+            self.relative_base,
+            self.instruction.to_string(),
+            operands.join(", ")
+        )?;
+        if let Some((address, value)) = self.write {
+            write!(f, " [{}] <- {}", address, value)?;
+        }
+        Ok(())
+    }
+}
+
+pub trait Tracer {
+    fn trace(&mut self, event: &TraceEvent);
+}
+
+/// Writes one line per executed instruction to a file.
+///
+/// Each line is written out as soon as it is traced, so the file is complete
+/// even when the program fails and the process exits with the tracer still
+/// alive.
+pub struct FileTracer {
+    writer: LineWriter<File>,
+}
+
+impl FileTracer {
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(FileTracer {
+            writer: LineWriter::new(File::create(path)?),
+        })
+    }
+
+    /// Creates a tracer for the file named by `INTCODE_TRACE`, if it is set.
+    pub fn from_env() -> Option<Self> {
+        let path = env::var_os(TRACE_ENV)?;
+        match FileTracer::create(&path) {
+            Ok(tracer) => Some(tracer),
+            Err(err) => {
+                eprintln!("Could not create trace file {:?}: {}", path, err);
+                None
+            }
+        }
+    }
+}
+
+impl Tracer for FileTracer {
+    fn trace(&mut self, event: &TraceEvent) {
+        // A failing trace shouldn't stop the program being traced.
+        let _ = writeln!(self.writer, "{}", event);
+    }
+}
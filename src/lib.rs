@@ -1,9 +1,11 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod days;
 pub mod intcode;
+pub mod nanofactory;
 pub mod parser;
 pub mod password;
+pub mod solver;
 pub mod sum;
 pub mod wires;
-pub mod nanofactory;
@@ -1,15 +1,22 @@
-use std::collections::{HashSet};
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 
-#[derive(Debug, Eq, Ord, Clone)]
+#[derive(Debug, Eq, Clone)]
 pub struct Term {
     pub chemical: String,
     pub amount: i64,
 }
 
+impl Ord for Term {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.chemical.cmp(&other.chemical)
+    }
+}
+
 impl PartialOrd for Term {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.chemical.partial_cmp(&other.chemical)
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -26,9 +33,23 @@ impl Hash for Term {
 }
 
 impl Term {
+    /// Parses a term like `7 ORE`.
+    pub fn parse(term: &str) -> Result<Term, String> {
+        let term = term.trim();
+        match term.find(' ') {
+            Some(i) => Ok(Term {
+                chemical: String::from(term[i..].trim()),
+                amount: term[..i]
+                    .parse()
+                    .map_err(|_| format!("Invalid amount in '{}'", term))?,
+            }),
+            None => Err(format!("Invalid term '{}'", term)),
+        }
+    }
+
     pub fn take(&mut self, amount: i64) -> i64 {
         if self.amount >= amount {
-            self.amount = self.amount - amount;
+            self.amount -= amount;
             0
         } else {
             let missing = amount - self.amount;
@@ -44,6 +65,23 @@ pub struct Reaction {
     pub from: Vec<Term>,
 }
 
+impl Reaction {
+    /// Parses a reaction like `7 A, 1 B => 1 C`.
+    pub fn parse(line: &str) -> Result<Reaction, String> {
+        let sides: Vec<&str> = line.splitn(2, " => ").collect();
+        if sides.len() != 2 {
+            return Err(format!("Invalid reaction '{}'", line));
+        }
+        Ok(Reaction {
+            to: Term::parse(sides[1])?,
+            from: sides[0]
+                .split(", ")
+                .map(Term::parse)
+                .collect::<Result<Vec<Term>, String>>()?,
+        })
+    }
+}
+
 impl Hash for Reaction {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.to.hash(state);
@@ -61,26 +99,34 @@ pub struct NanoFactory {
 }
 
 impl NanoFactory {
-    pub fn produce(&self, amount: i64, target: &Term, extra: HashSet<Term>) -> Result<(i64, HashSet<Term>), String> {
+    pub fn produce(
+        &self,
+        amount: i64,
+        target: &Term,
+        extra: HashSet<Term>,
+    ) -> Result<(i64, HashSet<Term>), String> {
         let reaction = self.reactions.get(&Reaction {
             to: target.clone(),
             from: Vec::new(),
         });
 
-        //println!("{:?}", reaction);
-
         match reaction {
             Some(r) => self.produce_reaction(amount, r, &extra),
-            None => Err("Not found".to_string())
+            None => Err("Not found".to_string()),
         }
     }
 
-    fn produce_reaction(&self, times: i64, reaction: &Reaction, extra_in: &HashSet<Term>) -> Result<(i64, HashSet<Term>), String> {
+    fn produce_reaction(
+        &self,
+        times: i64,
+        reaction: &Reaction,
+        extra_in: &HashSet<Term>,
+    ) -> Result<(i64, HashSet<Term>), String> {
         let mut result = 0;
         let mut extra = extra_in.clone();
         for item in reaction.from.iter() {
             let mut amount = item.amount * times;
-            
+
             let source = self.reactions.get(&Reaction {
                 to: item.clone(),
                 from: Vec::new(),
@@ -96,30 +142,29 @@ impl NanoFactory {
                         }
                     }
 
-                    let source_times = (amount as f32 / source_reaction.to.amount as f32).ceil() as i64;
+                    let produced = source_reaction.to.amount;
+                    let source_times = (amount + produced - 1) / produced;
                     let excess = source_times * source_reaction.to.amount - amount;
-                    let (output, extras) = self.produce_reaction(source_times, source_reaction, &extra)?;
+                    let (output, extras) =
+                        self.produce_reaction(source_times, source_reaction, &extra)?;
                     extra = extras;
                     if excess > 0 {
-                        let mut newExtra = excess;
+                        let mut new_extra = excess;
                         if let Some(prev) = extra.get(item) {
-                            newExtra = newExtra + prev.amount;
+                            new_extra += prev.amount;
                         }
-                        extra.replace(Term { chemical: item.chemical.clone(), amount: newExtra });
+                        extra.replace(Term {
+                            chemical: item.chemical.clone(),
+                            amount: new_extra,
+                        });
                     }
-                    result = result + output;
-                },
-                None => {
-                    //println!("Cost for {} {} is {}", times * reaction.to.amount, reaction.to.chemical, amount);
-                    //println!("Extra: {:?}", extra);
-                    return Ok((amount, extra));
+                    result += output;
                 }
+                // Nothing produces the raw material, it is used as is.
+                None => result += amount,
             }
         }
 
-        //println!("Cost for {} {} is {}", times * reaction.to.amount, reaction.to.chemical, result);
-        //println!("Extra: {:?}", extra);
-
         Ok((result, extra))
     }
 }
@@ -1,58 +1,297 @@
+use std::error::Error;
+use std::fmt;
 use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-pub fn print_args(args: &Vec<String>) {
+/// Directory searched for `dayNN.txt` when no input is given.
+pub const INPUTS_DIR: &str = "inputs";
+
+const USAGE: &str = "use '-f <filename>', '-' for stdin or '<value> [<value>]*'";
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    /// Values given on the command line, one per line.
+    Inline(Vec<String>),
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The arguments don't name an input.
+    Usage(String),
+    Io {
+        source: String,
+        err: io::Error,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Usage(msg) => write!(f, "Invalid arguments! {} ({})", msg, USAGE),
+            InputError::Io { source, err } => write!(f, "Could not read {}: {}", source, err),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io { err, .. } => Some(err),
+            InputError::Usage(_) => None,
+        }
+    }
+}
+
+impl Source {
+    /// Picks the input from the command line, program name first:
+    ///
+    /// * `-f <file>` reads the file, `-f -` or `-` reads stdin,
+    /// * anything else is taken as the input values,
+    /// * no arguments at all read `inputs/dayNN.txt` if `day` is given.
+    ///
+    /// Returns the source and the arguments following it, which are empty
+    /// for inline values.
+    pub fn from_args(args: &[String], day: Option<u32>) -> Result<(Source, &[String]), InputError> {
+        let args = args.get(1..).unwrap_or(&[]);
+
+        match args.first().map(String::as_str) {
+            Some("-f") => match args.get(1).map(String::as_str) {
+                Some("-") => Ok((Source::Stdin, &args[2..])),
+                Some(path) => Ok((Source::File(PathBuf::from(path)), &args[2..])),
+                None => Err(InputError::Usage("'-f' needs a file name".to_string())),
+            },
+            Some("-") => Ok((Source::Stdin, &args[1..])),
+            Some(_) => Ok((Source::Inline(args.to_vec()), &[])),
+            None => match day {
+                Some(day) => Ok((Source::File(default_input(day)), &[])),
+                None => Err(InputError::Usage("No input given".to_string())),
+            },
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Source::File(path) => fs::read_to_string(path).map_err(|err| InputError::Io {
+                source: format!("'{}'", path.display()),
+                err,
+            }),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|err| InputError::Io {
+                        source: "stdin".to_string(),
+                        err,
+                    })?;
+                Ok(input)
+            }
+            Source::Inline(values) => Ok(values.join("\n")),
+        }
+    }
+
+    /// Like `read`, but without requiring the input to be text.
+    pub fn read_bytes(&self) -> Result<Vec<u8>, InputError> {
+        match self {
+            Source::File(path) => fs::read(path).map_err(|err| InputError::Io {
+                source: format!("'{}'", path.display()),
+                err,
+            }),
+            Source::Stdin => {
+                let mut input = Vec::new();
+                io::stdin()
+                    .read_to_end(&mut input)
+                    .map_err(|err| InputError::Io {
+                        source: "stdin".to_string(),
+                        err,
+                    })?;
+                Ok(input)
+            }
+            Source::Inline(_) => self.read().map(String::into_bytes),
+        }
+    }
+}
+
+/// The input file used for `day` when none is given: `inputs/dayNN.txt`.
+pub fn default_input(day: u32) -> PathBuf {
+    PathBuf::from(INPUTS_DIR).join(format!("day{:02}.txt", day))
+}
+
+pub fn print_args(args: &[String]) {
     for arg in args {
         print!("{} ", arg);
     }
     println!();
 }
 
-pub fn parse_lines(args: &Vec<String>) -> Vec<String> {
-    parse_vals(args, read_lines)
+/// The whole input, for binaries that have no default input file.
+pub fn read_input(args: &[String]) -> Result<String, InputError> {
+    Source::from_args(args, None)?.0.read()
 }
 
-pub fn parse_comma_list(args: &Vec<String>) -> Vec<String> {
-    parse_vals(args, read_comma_list)
+/// The whole input for `day`, from wherever the arguments say.
+pub fn parse_input(args: &[String], day: u32) -> Result<String, InputError> {
+    parse_input_with_args(args, day).map(|(input, _)| input)
 }
 
-pub fn parse_digits(args: &Vec<String>) -> Vec<String> {
-    parse_vals(args, read_digits)
+/// Like `parse_input`, but also returns the arguments after the input, for
+/// binaries that take extra options.
+pub fn parse_input_with_args(args: &[String], day: u32) -> Result<(String, &[String]), InputError> {
+    let (source, rest) = Source::from_args(args, Some(day))?;
+    Ok((source.read()?, rest))
 }
 
-fn parse_vals<F>(args: &Vec<String>, parser: F) -> Vec<String>
+/// A malformed value in the input. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error in the first line, for parsers that only see one line.
+    pub fn new<T: AsRef<str>>(column: usize, message: T) -> Self {
+        ParseError {
+            line: 1,
+            column,
+            message: message.as_ref().to_string(),
+        }
+    }
+
+    /// Moves an error found in a slice of the input to where the slice starts:
+    /// `lines` lines and, on the first line, `columns` columns further in.
+    fn offset(mut self, lines: usize, columns: usize) -> Self {
+        if self.line == 1 {
+            self.column += columns;
+        }
+        self.line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses one value per non-empty line.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
 where
-    F: Fn(String) -> Vec<String>,
+    T: FromStr,
+    T::Err: fmt::Display,
 {
-    match args {
-        _ if args.len() < 2 => {
-            println!("Invalid arguments! (use '-f <filename>' or '<value> [, <value>]*'");
-            std::process::exit(0);
+    parse_records(input, |line| parse_value(line, 1))
+}
+
+/// Parses values separated by commas. Newlines separate values as well, and
+/// empty values, like the one after a trailing comma, are skipped.
+pub fn parse_comma_list<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let mut values = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let mut column = 1;
+        for field in line.split(',') {
+            let value = field.trim();
+            if !value.is_empty() {
+                let start = column + leading_columns(field);
+                values.push(parse_value(value, start).map_err(|e| e.offset(index, 0))?);
+            }
+            column += field.chars().count() + 1;
         }
-        _ if args[1] == "-f" && args.len() > 2 => parser(read_from_file(&args[2]).unwrap()),
-        _ => args.clone().into_iter().skip(1).collect(),
     }
+    Ok(values)
 }
 
-pub fn read_digits(s: String) -> Vec<String> {
-    s.chars()
-        .map(|c| String::from(format!("{}", c)))
-        .collect()
+/// Parses every digit in the input, ignoring whitespace.
+pub fn parse_digits(input: &str) -> Result<Vec<u32>, ParseError> {
+    let mut digits = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        for (column, c) in line.chars().enumerate().filter(|(_, c)| !c.is_whitespace()) {
+            let digit = c.to_digit(10).ok_or_else(|| {
+                ParseError::new(column + 1, format!("'{}' is not a digit", c)).offset(index, 0)
+            })?;
+            digits.push(digit);
+        }
+    }
+    Ok(digits)
+}
+
+/// Parses a rectangular grid with one character per cell, using the `FromStr`
+/// of `T` on each character. Returns the rows, top to bottom.
+pub fn parse_grid<T>(input: &str) -> Result<Vec<Vec<T>>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let rows = parse_records(input, |line| {
+        line.char_indices()
+            .enumerate()
+            .map(|(column, (i, c))| parse_value(&line[i..i + c.len_utf8()], column + 1))
+            .collect::<Result<Vec<T>, ParseError>>()
+    })?;
+
+    if let Some(first) = rows.first() {
+        let width = first.len();
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+        for (row, (index, _)) in rows.iter().zip(&mut lines) {
+            if row.len() != width {
+                return Err(ParseError::new(
+                    row.len().min(width) + 1,
+                    format!("row has {} cells, expected {}", row.len(), width),
+                )
+                .offset(index, 0));
+            }
+        }
+    }
+
+    Ok(rows)
 }
 
-pub fn read_comma_list(s: String) -> Vec<String> {
-    s.split(',')
-        .map(str::trim)
-        .filter_map(|s| match s.is_empty() {
-            true => None,
-            false => Some(String::from(s)),
+/// Parses every non-empty line with `parse_line`, which gets the line
+/// without surrounding whitespace. Errors it reports for the line are moved
+/// to the line's place in the input.
+pub fn parse_records<'a, T, F>(input: &'a str, mut parse_line: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&'a str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            parse_line(line.trim()).map_err(|e| e.offset(index, leading_columns(line)))
         })
         .collect()
 }
 
-pub fn read_lines(s: String) -> Vec<String> {
-    s.lines().map(String::from).collect()
+fn parse_value<T>(s: &str, column: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    s.parse()
+        .map_err(|e| ParseError::new(column, format!("invalid value '{}': {}", s, e)))
 }
 
-fn read_from_file(path: &str) -> std::io::Result<String> {
-    Ok(fs::read_to_string(path)?)
+fn leading_columns(s: &str) -> usize {
+    s.chars().take_while(|c| c.is_whitespace()).count()
 }
@@ -0,0 +1,113 @@
+use crate::intcode::IntCodeError;
+use crate::parser::ParseError;
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    /// Answers that have to be read off a rendered image.
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "\n{}", s),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+#[derive(Debug)]
+pub enum SolverError {
+    /// The puzzle input could not be parsed.
+    Parse(String),
+    IntCode(IntCodeError),
+    /// The input was valid but has no answer.
+    NoSolution(String),
+}
+
+impl SolverError {
+    pub fn parse<T: AsRef<str>>(msg: T) -> Self {
+        SolverError::Parse(msg.as_ref().to_string())
+    }
+
+    pub fn no_solution<T: AsRef<str>>(msg: T) -> Self {
+        SolverError::NoSolution(msg.as_ref().to_string())
+    }
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolverError::Parse(msg) => write!(f, "Invalid input: {}", msg),
+            SolverError::IntCode(err) => write!(f, "{}", err),
+            SolverError::NoSolution(msg) => write!(f, "No solution: {}", msg),
+        }
+    }
+}
+
+impl Error for SolverError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolverError::IntCode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<IntCodeError> for SolverError {
+    fn from(err: IntCodeError) -> Self {
+        SolverError::IntCode(err)
+    }
+}
+
+impl From<ParseIntError> for SolverError {
+    fn from(err: ParseIntError) -> Self {
+        SolverError::Parse(err.to_string())
+    }
+}
+
+impl From<ParseError> for SolverError {
+    fn from(err: ParseError) -> Self {
+        SolverError::Parse(err.to_string())
+    }
+}
+
+/// Solves both parts of one day's puzzle from the puzzle input.
+pub trait Solver {
+    fn part1(&self, input: &str) -> Result<Answer, SolverError>;
+    fn part2(&self, input: &str) -> Result<Answer, SolverError>;
+
+    /// The parts that are solved, as named on the command line.
+    fn parts(&self) -> &'static [char] {
+        &['a', 'b']
+    }
+}
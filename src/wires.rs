@@ -1,5 +1,6 @@
-use crate::parser;
+use crate::parser::{self, ParseError};
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone, Eq)]
 pub struct Position {
@@ -73,36 +74,30 @@ pub enum Move {
     Right(i32),
 }
 
-pub fn parse_move<'a, T>(r: T) -> Move
-where
-    T: AsRef<str> + 'a,
-{
-    let s = r.as_ref();
-    let val = str::parse::<i32>(&s[1..]).unwrap();
-    match &s[..1] {
-        "U" => Move::Up(val),
-        "D" => Move::Down(val),
-        "L" => Move::Left(val),
-        "R" => Move::Right(val),
-        _ => panic!("Not a valid move!: {}", s),
+impl FromStr for Move {
+    type Err = String;
+
+    /// Parses a move like `U7`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let direction = chars.next();
+        let val = chars.as_str().parse::<i32>().map_err(|e| e.to_string())?;
+        match direction {
+            Some('U') => Ok(Move::Up(val)),
+            Some('D') => Ok(Move::Down(val)),
+            Some('L') => Ok(Move::Left(val)),
+            Some('R') => Ok(Move::Right(val)),
+            _ => Err("not a direction".to_string()),
+        }
     }
 }
 
-pub fn get_wires(lines: Vec<String>) -> Vec<Vec<Position>> {
-    if lines.len() != 2 {
-        panic!("Need two sets of wires!");
-    }
-
-    let moves: Vec<Vec<Move>> = lines
-        .iter()
-        .map(|l| {
-            parser::read_comma_list(String::from(l))
-                .into_iter()
-                .map(parse_move)
-                .collect()
-        })
-        .collect();
+/// Parses one wire per line, as comma separated moves.
+pub fn parse_wires(input: &str) -> Result<Vec<Vec<Move>>, ParseError> {
+    parser::parse_records(input, parser::parse_comma_list)
+}
 
+pub fn get_wires(moves: &[Vec<Move>]) -> Vec<Vec<Position>> {
     let coords: Vec<Vec<Position>> = moves
         .iter()
         .map(|ms| {
@@ -0,0 +1,58 @@
+//! Runs every solver against the input/answer pairs listed in
+//! `tests/data/answers.txt` and reports each one.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::days;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn checked_in_answers() {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+    let manifest = fs::read_to_string(data.join("answers.txt")).unwrap();
+
+    let mut failures = Vec::new();
+    for (i, line) in manifest.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (day, part, file, expected) = match fields.as_slice() {
+            [day, part, file, expected] => (day.parse::<u32>().unwrap(), *part, *file, *expected),
+            _ => panic!(
+                "answers.txt:{}: expected '<day> <part> <file> <answer>'",
+                i + 1
+            ),
+        };
+
+        let solver = days::solver(day).unwrap_or_else(|| panic!("No solver for day {}", day));
+        let input = fs::read_to_string(data.join(file)).unwrap();
+        let result = match part {
+            "a" => solver.part1(&input),
+            "b" => solver.part2(&input),
+            _ => panic!("answers.txt:{}: part must be a or b", i + 1),
+        };
+
+        let actual = match result {
+            Ok(answer) => answer.to_string(),
+            Err(err) => format!("error: {}", err),
+        };
+        let status = match actual == expected {
+            true => "PASS",
+            false => "FAIL",
+        };
+        println!("{} day {:>2}{} {:<20} {}", status, day, part, file, actual);
+
+        if actual != expected {
+            failures.push(format!(
+                "day {}{} ({}): expected {}, got {}",
+                day, part, file, expected, actual
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,44 @@
+//! The `aoc` command line.
+
+use std::process::{Command, Output};
+
+fn aoc(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn help() {
+    for args in [&["help"][..], &["run", "--help"], &["run", "7", "-h"]].iter() {
+        let output = aoc(args);
+        assert!(output.status.success(), "{:?}: {:?}", args, output);
+        assert!(stdout(&output).starts_with("Usage:"), "{:?}", args);
+    }
+
+    let output = aoc(&["run", "x"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("'x' is not a day"));
+}
+
+#[test]
+fn list() {
+    let output = aoc(&["list"]);
+    assert!(output.status.success(), "{:?}", output);
+    let lines: Vec<String> = stdout(&output).lines().map(String::from).collect();
+    assert_eq!(lines.len(), 15);
+    assert_eq!(lines[0], "Day  1: a, b");
+    assert_eq!(lines[14], "Day 15: a, b");
+}
+
+#[test]
+fn run() {
+    let output = aoc(&["run", "1", "--part", "b", "14", "1969"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(stdout(&output), "968\n");
+}
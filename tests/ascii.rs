@@ -0,0 +1,85 @@
+//! ASCII mode I/O.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::ascii::{self, Chunk, LineInput, Lines};
+use advent_of_code_2019::intcode::{Engine, IntCodeError, Machine, StepResult};
+
+/// Prints a "> " prompt, echoes a line of input and outputs 1000.
+const ECHO: [i64; 18] = [
+    104, 62, 104, 32, // prompt
+    3, 100, 4, 100, // in [100], out [100]
+    1008, 100, 10, 101, 1006, 101, 4, // until a newline
+    104, 1000, 99,
+];
+
+fn line(text: &str) -> Chunk {
+    Chunk::Line(text.to_string())
+}
+
+#[test]
+fn encode() {
+    assert_eq!(ascii::encode("go"), vec![103, 111, 10]);
+    assert_eq!(ascii::encode("go\n"), vec![103, 111, 10]);
+    assert_eq!(ascii::encode(""), vec![10]);
+}
+
+#[test]
+fn decode() {
+    assert_eq!(
+        ascii::decode(&[72, 105, 10, 10, 33]),
+        vec![line("Hi"), line(""), line("!")]
+    );
+    // Values outside ASCII don't break the line they turn up in.
+    assert_eq!(
+        ascii::decode(&[65, 1000, 66, -1, 10]),
+        vec![Chunk::Value(1000), Chunk::Value(-1), line("AB")]
+    );
+    assert_eq!(Chunk::Value(19_349_530).to_string(), "19349530");
+}
+
+#[test]
+fn prompts() {
+    let mut machine = Machine::new(&ECHO[..]);
+    let mut lines = Lines::new();
+
+    assert_eq!(
+        machine.run_until_input_needed().unwrap(),
+        StepResult::NeedInput
+    );
+    for value in machine.take_output() {
+        lines.push(value);
+    }
+    assert_eq!(lines.pop(), None);
+    assert_eq!(lines.partial(), "> ");
+
+    for value in ascii::encode("hello") {
+        machine.push_input(value);
+    }
+    assert_eq!(
+        machine.run_until_input_needed().unwrap(),
+        StepResult::Halted
+    );
+    for value in machine.take_output() {
+        lines.push(value);
+    }
+    assert_eq!(lines.pop(), Some(line("> hello")));
+    assert_eq!(lines.pop(), Some(Chunk::Value(1000)));
+    assert_eq!(lines.pop(), None);
+}
+
+#[test]
+fn line_input() {
+    let mut lines = Lines::new();
+    Machine::new(&ECHO[..])
+        .run_io(&mut LineInput::new(&b"hi\r\nbye\n"[..]), &mut lines)
+        .unwrap();
+    lines.finish();
+    assert_eq!(lines.pop(), Some(line("> hi")));
+    assert_eq!(lines.pop(), Some(Chunk::Value(1000)));
+
+    match Machine::new(&ECHO[..]).run_io(&mut LineInput::new(&b""[..]), &mut Lines::new()) {
+        Err(IntCodeError::InputExhausted { pc }) => assert_eq!(pc, 4),
+        other => panic!("expected InputExhausted, got {:?}", other),
+    }
+}
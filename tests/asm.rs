@@ -0,0 +1,87 @@
+//! Assembling Intcode from source.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::asm::{self, AsmError};
+use advent_of_code_2019::intcode::{Engine, Machine, StepResult};
+
+/// Doubles every input value, forever.
+const DOUBLER: &str = "
+start:  IN   [n]
+        MUL  [n], #2, [n]
+        OUT  [n]
+        JT   #1, #start
+n:      .data 0
+";
+
+/// Relative mode on both sides of the base, and labels with offsets.
+const RELATIVE: &str = "
+        ARB  #buf           ; rb = buf
+        ADD  rb+0, #5, Rb+1
+        out  rb+1
+        OUT  [buf + 1]
+        OUT  rb-1
+        hlt
+        .data 7
+buf:    .data 10, 0
+";
+
+fn error(source: &str) -> AsmError {
+    asm::assemble(source).unwrap_err()
+}
+
+#[test]
+fn words() {
+    assert_eq!(
+        asm::assemble(DOUBLER).unwrap(),
+        vec![3, 11, 1002, 11, 2, 11, 4, 11, 1105, 1, 0, 0]
+    );
+    assert_eq!(
+        asm::assemble(RELATIVE).unwrap(),
+        vec![109, 14, 21201, 0, 5, 1, 204, 1, 4, 15, 204, -1, 99, 7, 10, 0]
+    );
+    assert_eq!(asm::to_comma_list(&[1, -2, 3]), "1,-2,3");
+}
+
+#[test]
+fn runs() {
+    let mut machine = Machine::new(asm::assemble(DOUBLER).unwrap());
+    machine.push_input(21);
+    machine.push_input(-4);
+    assert_eq!(
+        machine.run_until_input_needed().unwrap(),
+        StepResult::NeedInput
+    );
+    assert_eq!(machine.take_output(), vec![42, -8]);
+
+    let mut machine = Machine::new(asm::assemble(RELATIVE).unwrap());
+    machine.run().unwrap();
+    assert_eq!(machine.take_output(), vec![15, 15, 7]);
+}
+
+#[test]
+fn errors() {
+    let cases = [
+        ("OUT €5", 1, "Invalid operand '€5'"),
+        ("OUT r€", 1, "Invalid operand 'r€'"),
+        ("OUT rb€", 1, "Invalid operand 'rb€'"),
+        // Only numbers can be subtracted from the relative base.
+        ("n: OUT rb-n", 1, "Invalid operand 'rb-n'"),
+        ("\nJMP #0", 2, "Unknown mnemonic 'JMP'"),
+        ("ADD [1], [2]", 1, "ADD takes 3 operands, got 2"),
+        ("IN #5", 1, "IN cannot write to an immediate operand"),
+        ("a: HLT\na: HLT", 2, "Duplicate label 'a'"),
+        ("2a: HLT", 1, "Invalid label '2a'"),
+        ("HLT\nOUT [nowhere]", 2, "Unknown label 'nowhere'"),
+        (".data 1, x y", 1, "Invalid value 'x y'"),
+    ];
+    for &(source, line, message) in cases.iter() {
+        let err = error(source);
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (line, message),
+            "{:?}",
+            source
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! The Intcode to Rust compiler.
+//!
+//! The programs in `compiled/` were generated by `intcode-compile` and are run
+//! against the interpreter. `generated_code_is_current` fails when the
+//! compiler's output changes; regenerate them with e.g.
+//! `echo 1,9,10,3,2,3,11,0,99,30,40,50 | cargo run --bin intcode-compile -- --variables=1,2 -`.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{
+    compile, Arithmetic, Engine, FastMachine, IntCodeError, Machine,
+};
+
+#[rustfmt::skip]
+#[path = "compiled/compare.rs"]
+mod compare;
+#[rustfmt::skip]
+#[path = "compiled/countdown.rs"]
+mod countdown;
+#[rustfmt::skip]
+#[path = "compiled/day02.rs"]
+mod day02;
+#[rustfmt::skip]
+#[path = "compiled/jump.rs"]
+mod jump;
+#[rustfmt::skip]
+#[path = "compiled/overflow.rs"]
+mod overflow;
+#[rustfmt::skip]
+#[path = "compiled/quine.rs"]
+mod quine;
+
+type Compiled = fn(&mut FastMachine) -> Result<(), IntCodeError>;
+
+const DAY02: [i64; 12] = [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+
+const COMPARE: [i64; 47] = [
+    3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+    1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105,
+    1, 46, 98, 99,
+];
+
+const QUINE: [i64; 16] = [
+    109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+];
+
+/// Writes into its own first instruction, so the interpreter has to finish.
+const COUNTDOWN: [i64; 10] = [104, 3, 1001, 1, -1, 1, 1005, 1, 0, 99];
+
+/// Jumps to an address read from memory.
+const JUMP: [i64; 16] = [3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+
+/// Reads from below `i64::MIN`.
+const OVERFLOW: [i64; 5] = [109, -1, 204, i64::MIN, 99];
+
+/// Runs `machine` compiled and interpreted, checks the two agree and
+/// returns the outputs.
+fn run(compiled: Compiled, machine: FastMachine) -> Result<Vec<i64>, IntCodeError> {
+    let mut interpreted = machine.clone();
+    let expected = interpreted.run();
+
+    let mut machine = machine;
+    let result = compiled(&mut machine);
+    match (&expected, &result) {
+        (Ok(()), Ok(())) => {}
+        (Err(expected), Err(err)) => assert_eq!(err.to_string(), expected.to_string()),
+        _ => panic!("compiled {:?}, interpreted {:?}", result, expected),
+    }
+    assert_eq!(machine.pc(), interpreted.pc());
+    assert_eq!(machine.is_halted(), interpreted.is_halted());
+    assert_eq!(
+        machine.to_memory().to_vec(),
+        interpreted.to_memory().to_vec()
+    );
+
+    let output = machine.take_output();
+    assert_eq!(output, interpreted.take_output());
+    result.map(|_| output)
+}
+
+fn with_input(program: &[i64], input: &[i64]) -> FastMachine {
+    let mut machine = FastMachine::new(program);
+    for &value in input {
+        machine.push_input(value);
+    }
+    machine
+}
+
+#[test]
+fn generated_code_is_current() {
+    let cases: [(&str, &[i64], &[usize]); 6] = [
+        (include_str!("compiled/compare.rs"), &COMPARE, &[]),
+        (include_str!("compiled/countdown.rs"), &COUNTDOWN, &[]),
+        (include_str!("compiled/day02.rs"), &DAY02, &[1, 2]),
+        (include_str!("compiled/jump.rs"), &JUMP, &[]),
+        (include_str!("compiled/overflow.rs"), &OVERFLOW, &[]),
+        (include_str!("compiled/quine.rs"), &QUINE, &[]),
+    ];
+    for &(source, program, variables) in cases.iter() {
+        assert_eq!(compile::to_rust(program, variables), source);
+    }
+}
+
+#[test]
+fn variables_are_read_at_run_time() {
+    for &(noun, verb) in [(9, 10), (0, 0), (11, 5), (3, 3)].iter() {
+        let mut machine = FastMachine::new(&DAY02[..]);
+        machine.set(1, noun);
+        machine.set(2, verb);
+        run(day02::run, machine.clone()).unwrap();
+
+        let mut reference = Machine::new(&DAY02[..]);
+        reference.memory_mut()[1] = noun;
+        reference.memory_mut()[2] = verb;
+        reference.run().unwrap();
+
+        day02::run(&mut machine).unwrap();
+        assert_eq!(machine.get(0), reference.memory()[0]);
+    }
+
+    // A negative address is left to the interpreter to report.
+    let mut machine = FastMachine::new(&DAY02[..]);
+    machine.set(1, -4);
+    assert!(matches!(
+        run(day02::run, machine),
+        Err(IntCodeError::AddressOutOfRange { address: -4, .. })
+    ));
+}
+
+#[test]
+fn relative_overflow() {
+    assert!(matches!(
+        run(overflow::run, FastMachine::new(&OVERFLOW[..])),
+        Err(IntCodeError::AddressOutOfRange {
+            pc: 2,
+            address: i64::MIN,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn jumps_and_input() {
+    for &(input, expected) in [(-3, 999), (7, 999), (8, 1000), (9, 1001)].iter() {
+        let output = run(compare::run, with_input(&COMPARE, &[input])).unwrap();
+        assert_eq!(output, vec![expected]);
+    }
+    match run(compare::run, with_input(&COMPARE, &[])) {
+        Err(IntCodeError::InputExhausted { pc }) => assert_eq!(pc, 0),
+        other => panic!("expected InputExhausted, got {:?}", other),
+    }
+
+    assert_eq!(
+        run(quine::run, FastMachine::new(&QUINE[..])).unwrap(),
+        QUINE
+    );
+}
+
+#[test]
+fn falls_back_to_the_interpreter() {
+    assert_eq!(
+        run(countdown::run, FastMachine::new(&COUNTDOWN[..])).unwrap(),
+        vec![3, 2, 1]
+    );
+
+    for &(input, expected) in [(0, 0), (5, 1)].iter() {
+        assert_eq!(
+            run(jump::run, with_input(&JUMP, &[input])).unwrap(),
+            vec![expected]
+        );
+    }
+
+    // Overflow is up to the machine's arithmetic.
+    let mut machine = FastMachine::new(&DAY02[..]);
+    machine.set(9, i64::MAX);
+    assert!(matches!(
+        run(day02::run, machine.clone()),
+        Err(IntCodeError::Overflow { pc: 0, .. })
+    ));
+    machine.set_arithmetic(Arithmetic::Wrapping);
+    run(day02::run, machine.clone()).unwrap();
+    day02::run(&mut machine).unwrap();
+    assert_eq!(machine.get(3), i64::MIN + 39);
+
+    // Resuming a machine the interpreter has already started.
+    let mut machine = with_input(&COMPARE, &[8]);
+    machine.step().unwrap();
+    machine.step().unwrap();
+    assert_eq!(run(compare::run, machine).unwrap(), vec![1000]);
+}
@@ -0,0 +1,129 @@
+// Generated by intcode-compile, do not edit.
+
+#![allow(dead_code, unused_assignments, unused_mut, unreachable_code, clippy::all)]
+
+use advent_of_code_2019::intcode::{Engine, FastMachine, IntCodeError};
+
+/// The words holding compiled instructions.
+const CODE: &[(i64, i64)] = &[(0, 19), (22, 45), (46, 47)];
+
+fn in_code(address: i64) -> bool {
+    CODE.iter().any(|&(start, end)| start <= address && address < end)
+}
+
+pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError> {
+    if m.is_halted() {
+        return Ok(());
+    }
+    let mut pc = m.pc();
+    let mut rb = m.relative_base();
+    'run: loop {
+        match pc {
+            0 => {
+                // 0: IN   [21]
+                pc = 0;
+                let value = match m.pop_input() {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(21, value);
+                // 2: EQ   [21], #8, [20]
+                pc = 2;
+                m.set(20, (m.get(21) == 8) as i64);
+                // 6: JT   [20], #22
+                pc = 6;
+                if m.get(20) != 0 {
+                    pc = 22;
+                    continue 'run;
+                }
+                pc = 9;
+                continue 'run;
+            }
+            9 => {
+                // 9: LT   #8, [21], [20]
+                pc = 9;
+                m.set(20, (8 < m.get(21)) as i64);
+                // 13: JF   [20], #31
+                pc = 13;
+                if m.get(20) == 0 {
+                    pc = 31;
+                    continue 'run;
+                }
+                pc = 16;
+                continue 'run;
+            }
+            16 => {
+                // 16: JF   #0, #36
+                pc = 16;
+                if 0 == 0 {
+                    pc = 36;
+                    continue 'run;
+                }
+                pc = 19;
+                break 'run;
+            }
+            22 => {
+                // 22: MUL  [21], #125, [20]
+                pc = 22;
+                let value = match i64::checked_mul(m.get(21), 125) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(20, value);
+                // 26: OUT  [20]
+                pc = 26;
+                m.push_output(m.get(20));
+                // 28: JT   #1, #46
+                pc = 28;
+                if 1 != 0 {
+                    pc = 46;
+                    continue 'run;
+                }
+                pc = 31;
+                continue 'run;
+            }
+            31 => {
+                // 31: OUT  #999
+                pc = 31;
+                m.push_output(999);
+                // 33: JT   #1, #46
+                pc = 33;
+                if 1 != 0 {
+                    pc = 46;
+                    continue 'run;
+                }
+                pc = 36;
+                continue 'run;
+            }
+            36 => {
+                // 36: ADD  #1000, #1, [20]
+                pc = 36;
+                let value = match i64::checked_add(1000, 1) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(20, value);
+                // 40: OUT  [20]
+                pc = 40;
+                m.push_output(m.get(20));
+                // 42: JT   #1, #46
+                pc = 42;
+                if 1 != 0 {
+                    pc = 46;
+                    continue 'run;
+                }
+                pc = 45;
+                break 'run;
+            }
+            46 => {
+                // 46: HLT
+                pc = 46;
+                break 'run;
+            }
+            _ => break 'run,
+        }
+    }
+    m.set_pc(pc);
+    m.set_relative_base(rb);
+    m.run()
+}
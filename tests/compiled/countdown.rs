@@ -0,0 +1,47 @@
+// Generated by intcode-compile, do not edit.
+
+#![allow(dead_code, unused_assignments, unused_mut, unreachable_code, clippy::all)]
+
+use advent_of_code_2019::intcode::{Engine, FastMachine, IntCodeError};
+
+/// The words holding compiled instructions.
+const CODE: &[(i64, i64)] = &[(0, 10)];
+
+fn in_code(address: i64) -> bool {
+    CODE.iter().any(|&(start, end)| start <= address && address < end)
+}
+
+pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError> {
+    if m.is_halted() {
+        return Ok(());
+    }
+    let mut pc = m.pc();
+    let mut rb = m.relative_base();
+    'run: loop {
+        match pc {
+            0 => {
+                // 0: OUT  #3
+                pc = 0;
+                m.push_output(3);
+                // 2: ADD  [1], #-1, [1]
+                pc = 2;
+                let value = match i64::checked_add(m.get(1), -1) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(1, value);
+                pc = 6;
+                break 'run;
+            }
+            9 => {
+                // 9: HLT
+                pc = 9;
+                break 'run;
+            }
+            _ => break 'run,
+        }
+    }
+    m.set_pc(pc);
+    m.set_relative_base(rb);
+    m.run()
+}
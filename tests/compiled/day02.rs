@@ -0,0 +1,55 @@
+// Generated by intcode-compile, do not edit.
+
+#![allow(dead_code, unused_assignments, unused_mut, unreachable_code, clippy::all)]
+
+use advent_of_code_2019::intcode::{Engine, FastMachine, IntCodeError};
+
+/// The words holding compiled instructions.
+const CODE: &[(i64, i64)] = &[(0, 1), (3, 9)];
+
+fn in_code(address: i64) -> bool {
+    CODE.iter().any(|&(start, end)| start <= address && address < end)
+}
+
+pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError> {
+    if m.is_halted() {
+        return Ok(());
+    }
+    let mut pc = m.pc();
+    let mut rb = m.relative_base();
+    'run: loop {
+        match pc {
+            0 => {
+                // 0: ADD  [9], [10], [3]
+                pc = 0;
+                let a1 = m.get(1);
+                if a1 < 0 {
+                    break 'run;
+                }
+                let a2 = m.get(2);
+                if a2 < 0 {
+                    break 'run;
+                }
+                let value = match i64::checked_add(m.get(a1 as usize), m.get(a2 as usize)) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(3, value);
+                // 4: MUL  [3], [11], [0]
+                pc = 4;
+                let value = match i64::checked_mul(m.get(3), m.get(11)) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(0, value);
+                // 8: HLT
+                pc = 8;
+                break 'run;
+            }
+            _ => break 'run,
+        }
+    }
+    m.set_pc(pc);
+    m.set_relative_base(rb);
+    m.run()
+}
@@ -0,0 +1,64 @@
+// Generated by intcode-compile, do not edit.
+
+#![allow(dead_code, unused_assignments, unused_mut, unreachable_code, clippy::all)]
+
+use advent_of_code_2019::intcode::{Engine, FastMachine, IntCodeError};
+
+/// The words holding compiled instructions.
+const CODE: &[(i64, i64)] = &[(0, 12)];
+
+fn in_code(address: i64) -> bool {
+    CODE.iter().any(|&(start, end)| start <= address && address < end)
+}
+
+pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError> {
+    if m.is_halted() {
+        return Ok(());
+    }
+    let mut pc = m.pc();
+    let mut rb = m.relative_base();
+    'run: loop {
+        match pc {
+            0 => {
+                // 0: IN   [12]
+                pc = 0;
+                let value = match m.pop_input() {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(12, value);
+                // 2: JF   [12], [15]
+                pc = 2;
+                if m.get(12) == 0 {
+                    let target = m.get(15);
+                    if target < 0 {
+                        break 'run;
+                    }
+                    pc = target as usize;
+                    continue 'run;
+                }
+                pc = 5;
+                continue 'run;
+            }
+            5 => {
+                // 5: ADD  [13], [14], [13]
+                pc = 5;
+                let value = match i64::checked_add(m.get(13), m.get(14)) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(13, value);
+                // 9: OUT  [13]
+                pc = 9;
+                m.push_output(m.get(13));
+                // 11: HLT
+                pc = 11;
+                break 'run;
+            }
+            _ => break 'run,
+        }
+    }
+    m.set_pc(pc);
+    m.set_relative_base(rb);
+    m.run()
+}
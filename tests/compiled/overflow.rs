@@ -0,0 +1,49 @@
+// Generated by intcode-compile, do not edit.
+
+#![allow(dead_code, unused_assignments, unused_mut, unreachable_code, clippy::all)]
+
+use advent_of_code_2019::intcode::{Engine, FastMachine, IntCodeError};
+
+/// The words holding compiled instructions.
+const CODE: &[(i64, i64)] = &[(0, 5)];
+
+fn in_code(address: i64) -> bool {
+    CODE.iter().any(|&(start, end)| start <= address && address < end)
+}
+
+pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError> {
+    if m.is_halted() {
+        return Ok(());
+    }
+    let mut pc = m.pc();
+    let mut rb = m.relative_base();
+    'run: loop {
+        match pc {
+            0 => {
+                // 0: ARB  #-1
+                pc = 0;
+                rb = match i64::checked_add(rb, -1) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                // 2: OUT  rb-9223372036854775808
+                pc = 2;
+                let a1 = match i64::checked_add(rb, -9223372036854775808) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                if a1 < 0 {
+                    break 'run;
+                }
+                m.push_output(m.get(a1 as usize));
+                // 4: HLT
+                pc = 4;
+                break 'run;
+            }
+            _ => break 'run,
+        }
+    }
+    m.set_pc(pc);
+    m.set_relative_base(rb);
+    m.run()
+}
@@ -0,0 +1,69 @@
+// Generated by intcode-compile, do not edit.
+
+#![allow(dead_code, unused_assignments, unused_mut, unreachable_code, clippy::all)]
+
+use advent_of_code_2019::intcode::{Engine, FastMachine, IntCodeError};
+
+/// The words holding compiled instructions.
+const CODE: &[(i64, i64)] = &[(0, 16)];
+
+fn in_code(address: i64) -> bool {
+    CODE.iter().any(|&(start, end)| start <= address && address < end)
+}
+
+pub fn run(m: &mut FastMachine) -> Result<(), IntCodeError> {
+    if m.is_halted() {
+        return Ok(());
+    }
+    let mut pc = m.pc();
+    let mut rb = m.relative_base();
+    'run: loop {
+        match pc {
+            0 => {
+                // 0: ARB  #1
+                pc = 0;
+                rb = match i64::checked_add(rb, 1) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                // 2: OUT  rb-1
+                pc = 2;
+                let a1 = match i64::checked_add(rb, -1) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                if a1 < 0 {
+                    break 'run;
+                }
+                m.push_output(m.get(a1 as usize));
+                // 4: ADD  [100], #1, [100]
+                pc = 4;
+                let value = match i64::checked_add(m.get(100), 1) {
+                    Some(value) => value,
+                    None => break 'run,
+                };
+                m.set(100, value);
+                // 8: EQ   [100], #16, [101]
+                pc = 8;
+                m.set(101, (m.get(100) == 16) as i64);
+                // 12: JF   [101], #0
+                pc = 12;
+                if m.get(101) == 0 {
+                    pc = 0;
+                    continue 'run;
+                }
+                pc = 15;
+                continue 'run;
+            }
+            15 => {
+                // 15: HLT
+                pc = 15;
+                break 'run;
+            }
+            _ => break 'run,
+        }
+    }
+    m.set_pc(pc);
+    m.set_relative_base(rb);
+    m.run()
+}
@@ -0,0 +1,103 @@
+//! The `intcode-debug` command line, driven through its stdin.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs the debugger on `program`, saved as `name`, with `commands` and
+/// returns what it printed, without the prompts.
+fn debug(name: &str, program: &str, commands: &str) -> String {
+    let path = env::temp_dir().join(format!("intcode-debug-{}-{}", std::process::id(), name));
+    fs::write(&path, program).unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_intcode-debug"))
+        .arg("-f")
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(commands.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .replace("(intcode) ", "")
+}
+
+#[test]
+fn list() {
+    let output = debug(
+        "list",
+        "1101,2,3,8,4,8,99",
+        "list 2 5\nlist 18446744073709551615\n",
+    );
+    // Listings stop at the end of memory.
+    let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    assert_eq!(
+        lines,
+        [
+            " >     0: 1101,2,3,8                  ADD  #2, #3, [8]",
+            "       2: 3,8                         IN   [8]",
+            "       4: 4,8                         OUT  [8]",
+            "       6: 99                          HLT",
+        ]
+    );
+}
+
+#[test]
+fn print() {
+    let program: Vec<String> = (0..25).map(|n| n.to_string()).collect();
+    let output = debug(
+        "print",
+        &program.join(","),
+        "print mem[3]\nprint mem[100]\nprint mem[18..100000000000]\n\
+         print mem[18446744073709551615]\nprint mem[30..40]\nprint mem[2..4]\n",
+    );
+    assert_eq!(
+        output.lines().skip(1).collect::<Vec<_>>(),
+        [
+            "mem[3] = 3",
+            "mem[100] = 0",
+            "mem[18..25] = 18,19,20,21,22,23,24",
+            "Address 18446744073709551615 is out of range",
+            "mem[30..] is past the end of memory at 25",
+            "mem[2..4] = 2,3",
+        ]
+    );
+
+    let output = debug("rows", &program.join(","), "print mem[0..25]\n");
+    assert_eq!(
+        output.lines().skip(1).collect::<Vec<_>>(),
+        [
+            "mem[0..10] = 0,1,2,3,4,5,6,7,8,9",
+            "mem[10..20] = 10,11,12,13,14,15,16,17,18,19",
+            "mem[20..25] = 20,21,22,23,24",
+        ]
+    );
+}
+
+#[test]
+fn set() {
+    let output = debug(
+        "set",
+        "1,0,0,0,99",
+        "set mem[1] 7\nset mem[0..3] 5\nset mem[18446744073709551615] 1\nprint mem[0..3]\n",
+    );
+    assert_eq!(
+        output.lines().skip(1).collect::<Vec<_>>(),
+        [
+            "Can only set one address at a time, got 'mem[0..3]'",
+            "Address 18446744073709551615 is out of range",
+            "mem[0..3] = 1,7,0",
+        ]
+    );
+}
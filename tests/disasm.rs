@@ -0,0 +1,73 @@
+//! Disassembly listings.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::disasm::{self, Line};
+use advent_of_code_2019::intcode::{Engine, Machine};
+
+/// Always jumps over an `OUT #1`, then computes the address of an `OUT rb-2`
+/// and jumps to it through memory. The last two words are data.
+const PROGRAM: [i64; 22] = [
+    109, 5, // ARB #5
+    1106, 0, 9, // JF #0, #9
+    104, 1, 99, 0, // never run
+    1101, 0, 17, 21, // ADD #0, #17, [21]
+    5, 20, 21, // JT [20], [21]
+    99, // HLT
+    204, -2, 99, // only reached through the jump above
+    1, 0,
+];
+
+fn listing(lines: Vec<Line>) -> String {
+    lines
+        .iter()
+        .map(|line| line.to_string().trim_end().to_string() + "\n")
+        .collect()
+}
+
+#[test]
+fn linear() {
+    assert_eq!(
+        listing(disasm::linear(&PROGRAM[..].into())),
+        "     0: 109,5                       ARB  #5
+     2: 1106,0,9                    JF   #0, #9
+     5: 104,1                       OUT  #1
+     7: 99                          HLT
+     8: 0                           DATA 0
+     9: 1101,0,17,21                ADD  #0, #17, [21]
+    13: 5,20,21                     JT   [20], [21]
+    16: 99                          HLT
+    17: 204,-2                      OUT  rb-2
+    19: 99                          HLT
+    20: 1                           DATA 1
+    21: 0                           DATA 0
+"
+    );
+}
+
+#[test]
+fn follow_control_flow() {
+    assert_eq!(
+        listing(disasm::follow_control_flow(&PROGRAM[..].into())),
+        "     0: 109,5                       ARB  #5
+     2: 1106,0,9                    JF   #0, #9
+     5: 104                         DATA 104
+     6: 1                           DATA 1
+     7: 99                          DATA 99
+     8: 0                           DATA 0
+     9: 1101,0,17,21                ADD  #0, #17, [21]
+    13: 5,20,21                     JT   [20], [21]
+    16: 99                          HLT
+    17: 204                         DATA 204
+    18: -2                          DATA -2
+    19: 99                          DATA 99
+    20: 1                           DATA 1
+    21: 0                           DATA 0
+"
+    );
+
+    // The code listed as data does run.
+    let mut machine = Machine::new(&PROGRAM[..]);
+    machine.run().unwrap();
+    assert_eq!(machine.take_output(), vec![0]);
+}
@@ -0,0 +1,381 @@
+//! The worked examples from each day's puzzle statement.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::days::*;
+use advent_of_code_2019::intcode::{Engine, Machine};
+use advent_of_code_2019::password::Password;
+use advent_of_code_2019::solver::{Answer, Solver};
+
+fn part1<S: Solver>(solver: &S, input: &str) -> Answer {
+    solver.part1(input).unwrap()
+}
+
+fn part2<S: Solver>(solver: &S, input: &str) -> Answer {
+    solver.part2(input).unwrap()
+}
+
+fn n(value: i64) -> Answer {
+    Answer::Number(value)
+}
+
+#[test]
+fn day01() {
+    let solver = day01::Day01;
+    assert_eq!(part1(&solver, "12"), n(2));
+    assert_eq!(part1(&solver, "14"), n(2));
+    assert_eq!(part1(&solver, "1969"), n(654));
+    assert_eq!(part1(&solver, "100756"), n(33583));
+    assert_eq!(part1(&solver, "12\n14\n1969\n100756\n"), n(34241));
+
+    assert_eq!(part2(&solver, "14"), n(2));
+    assert_eq!(part2(&solver, "1969"), n(966));
+    assert_eq!(part2(&solver, "100756"), n(50346));
+}
+
+#[test]
+fn day02() {
+    let program = [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+    assert_eq!(day02::run_with(&program, 9, 10).unwrap(), 3500);
+    assert_eq!(day02::run_with(&[1, 0, 0, 0, 99], 0, 0).unwrap(), 2);
+    assert_eq!(day02::run_with(&[2, 3, 0, 3, 99], 3, 0).unwrap(), 2);
+    assert_eq!(
+        day02::run_with(&[1, 1, 1, 4, 99, 5, 6, 0, 99], 1, 1).unwrap(),
+        30
+    );
+
+    // mem[0] = mem[noun] + mem[verb] with mem[a] = a past the program, so the
+    // first match is noun 2 (holding the verb) and verb 75.
+    let mut program: Vec<i64> = (0..100).collect();
+    program[..5].copy_from_slice(&[1, 0, 0, 0, 99]);
+    assert_eq!(day02::find_noun_verb(&program, 150, None), Some((2, 75)));
+    assert_eq!(day02::find_noun_verb(&program, 1000, None), None);
+}
+
+#[test]
+fn day03() {
+    let solver = day03::Day03;
+    let examples = [
+        ("R8,U5,L5,D3\nU7,R6,D4,L4", 6, 30),
+        (
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+            159,
+            610,
+        ),
+        (
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+            135,
+            410,
+        ),
+    ];
+
+    for &(input, distance, steps) in examples.iter() {
+        assert_eq!(part1(&solver, input), n(distance), "{}", input);
+        assert_eq!(part2(&solver, input), n(steps), "{}", input);
+    }
+}
+
+#[test]
+fn day04() {
+    assert!(Password::new(111_111).is_valid());
+    assert!(!Password::new(223_450).is_valid());
+    assert!(!Password::new(123_789).is_valid());
+
+    assert!(Password::new(112_233).is_strictly_valid());
+    assert!(!Password::new(123_444).is_strictly_valid());
+    assert!(Password::new(111_122).is_strictly_valid());
+
+    let solver = day04::Day04;
+    assert_eq!(part1(&solver, "111111-111122"), n(10));
+    assert_eq!(part2(&solver, "111111-111122"), n(1));
+}
+
+#[test]
+fn day05() {
+    let run = |program: &[i64], input: i64| {
+        day05::run_diagnostic(&mut Machine::new(program), input).unwrap()
+    };
+
+    // Equal to / less than 8, in position and immediate mode.
+    let equal_position = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+    let less_position = [3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
+    let equal_immediate = [3, 3, 1108, -1, 8, 3, 4, 3, 99];
+    let less_immediate = [3, 3, 1107, -1, 8, 3, 4, 3, 99];
+    for &(input, equal, less) in [(7, 0, 1), (8, 1, 0), (9, 0, 0)].iter() {
+        assert_eq!(run(&equal_position, input), equal);
+        assert_eq!(run(&equal_immediate, input), equal);
+        assert_eq!(run(&less_position, input), less);
+        assert_eq!(run(&less_immediate, input), less);
+    }
+
+    // Jumps: 0 if the input was zero, 1 otherwise.
+    let jump_position = [3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+    let jump_immediate = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+    assert_eq!(run(&jump_position, 0), 0);
+    assert_eq!(run(&jump_position, 5), 1);
+    assert_eq!(run(&jump_immediate, 0), 0);
+    assert_eq!(run(&jump_immediate, 5), 1);
+
+    let compare = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+    assert_eq!(run(&compare, 7), 999);
+    assert_eq!(run(&compare, 8), 1000);
+    assert_eq!(run(&compare, 9), 1001);
+
+    let solver = day05::Day05;
+    assert_eq!(part1(&solver, "3,0,4,0,99"), n(1));
+    assert_eq!(part2(&solver, "3,0,4,0,99"), n(5));
+    assert!(solver.part1("104,1,104,0,99").is_err());
+}
+
+#[test]
+fn day06() {
+    let solver = day06::Day06;
+    let orbits = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L";
+    assert_eq!(part1(&solver, orbits), n(42));
+
+    let transfers = format!("{}\nK)YOU\nI)SAN", orbits);
+    assert_eq!(part2(&solver, &transfers), n(4));
+    assert!(solver.part2(orbits).is_err());
+}
+
+#[test]
+fn day07() {
+    let solver = day07::Day07;
+    assert_eq!(
+        part1(&solver, "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0"),
+        n(43210)
+    );
+    assert_eq!(
+        part1(
+            &solver,
+            "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0"
+        ),
+        n(54321)
+    );
+    assert_eq!(
+        part1(
+            &solver,
+            "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,\
+             31,4,31,99,0,0,0"
+        ),
+        n(65210)
+    );
+
+    let feedback = [
+        3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28,
+        1005, 28, 6, 99, 0, 0, 5,
+    ];
+    assert_eq!(
+        day07::max_signal(&feedback, &[5, 6, 7, 8, 9]).unwrap(),
+        (vec![9, 8, 7, 6, 5], 139_629_729)
+    );
+    for &threads in [1, 3, 8].iter() {
+        assert_eq!(
+            day07::max_signal_parallel(&feedback, &[5, 6, 7, 8, 9], threads).unwrap(),
+            (vec![9, 8, 7, 6, 5], 139_629_729)
+        );
+    }
+
+    // More amplifiers than the puzzle, on more threads than batches.
+    let chain = [
+        3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+    ];
+    let sequential = day07::max_signal(&chain, &[0, 1, 2, 3, 4, 5, 6]).unwrap();
+    assert_eq!(sequential, (vec![6, 5, 4, 3, 2, 1, 0], 6_543_210));
+    assert_eq!(
+        day07::max_signal_parallel(&chain, &[6, 0, 5, 1, 4, 2, 3], 4).unwrap(),
+        sequential
+    );
+    assert!(day07::max_signal_parallel(&chain, &[], 2).is_err());
+    assert_eq!(
+        part2(
+            &solver,
+            "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,\
+             1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,\
+             0,0,10"
+        ),
+        n(18216)
+    );
+}
+
+#[test]
+fn day08() {
+    let solver = day08::Day08 {
+        width: 3,
+        height: 2,
+    };
+    assert_eq!(part1(&solver, "123456789012"), n(1));
+
+    let solver = day08::Day08 {
+        width: 2,
+        height: 2,
+    };
+    assert_eq!(
+        part2(&solver, "0222112222120000"),
+        Answer::Text(" *\n* ".to_string())
+    );
+    assert!(solver.part1("01201").is_err());
+
+    let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let size = |a: &[&str]| day08::Day08::from_args(&args(a)).map(|d| (d.width, d.height));
+    assert_eq!(size(&["3", "2"]), Some((3, 2)));
+    assert_eq!(size(&[]), Some((25, 6)));
+    assert_eq!(size(&["3", "y"]), None);
+}
+
+#[test]
+fn day09() {
+    let quine = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let mut machine = Machine::new(&quine[..]);
+    machine.run().unwrap();
+    assert_eq!(machine.take_output(), quine.to_vec());
+
+    let mut machine = Machine::new(vec![1102, 34_915_192, 34_915_192, 7, 4, 7, 99, 0]);
+    machine.run().unwrap();
+    assert_eq!(machine.take_output()[0].to_string().len(), 16);
+
+    let mut machine = Machine::new(vec![104, 1_125_899_906_842_624, 99]);
+    machine.run().unwrap();
+    assert_eq!(machine.take_output(), vec![1_125_899_906_842_624]);
+
+    let solver = day09::Day09;
+    assert_eq!(part1(&solver, "109,7,203,0,204,0,99"), n(1));
+    assert_eq!(part2(&solver, "109,7,203,0,204,0,99"), n(2));
+}
+
+#[test]
+fn day10() {
+    let small = ".#..#\n.....\n#####\n....#\n...##";
+    let asteroids = day10::asteroids(small).unwrap();
+    assert_eq!(day10::best_position(&asteroids), Some(((3, 4), 8)));
+    assert_eq!(part1(&day10::Day10, small), n(8));
+
+    let medium = "\
+......#.#.
+#..#.#....
+..#######.
+.#.#.###..
+.#..#.....
+..#....#.#
+#..#....#.
+.##.#..###
+##...#..#.
+.#....####";
+    let asteroids = day10::asteroids(medium).unwrap();
+    assert_eq!(day10::best_position(&asteroids), Some(((5, 8), 33)));
+
+    let laser = "\
+.#....#####...#..
+##...##.#####..##
+##...#...#.#####.
+..#.....#...###..
+..#.#.....#....##";
+    let asteroids = day10::asteroids(laser).unwrap();
+    let order = day10::vaporize_order(&asteroids, (8, 3));
+    assert_eq!(
+        order[..9].to_vec(),
+        vec![
+            (8, 1),
+            (9, 0),
+            (9, 1),
+            (10, 0),
+            (9, 2),
+            (11, 1),
+            (12, 1),
+            (11, 2),
+            (15, 1)
+        ]
+    );
+    assert_eq!(order.len(), asteroids.len() - 1);
+    assert_eq!(order.last(), Some(&(14, 3)));
+}
+
+/// A program that ignores the camera and sends the given outputs, one pair per
+/// input.
+fn robot(outputs: &[(i64, i64)]) -> String {
+    let mut program: Vec<String> = Vec::new();
+    for &(color, turn) in outputs {
+        program.push(format!("3,1000,104,{},104,{}", color, turn));
+    }
+    program.push("99".to_string());
+    program.join(",")
+}
+
+#[test]
+fn day11() {
+    let solver = day11::Day11;
+    let program = robot(&[(1, 0), (0, 0), (1, 0), (1, 0), (0, 1), (1, 0), (1, 0)]);
+    assert_eq!(part1(&solver, &program), n(6));
+
+    let program = robot(&[(1, 1); 4]);
+    assert_eq!(part2(&solver, &program), Answer::Text("**\n**".to_string()));
+}
+
+#[test]
+fn day12() {
+    let first = "<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n<x=4, y=-8, z=8>\n<x=3, y=5, z=-1>";
+    let second = "<x=-8, y=-10, z=0>\n<x=5, y=5, z=10>\n<x=2, y=-7, z=3>\n<x=9, y=-8, z=-3>";
+
+    assert_eq!(part1(&day12::Day12 { steps: 10 }, first), n(179));
+    assert_eq!(part1(&day12::Day12 { steps: 100 }, second), n(1940));
+
+    assert_eq!(part2(&day12::Day12::default(), first), n(2772));
+    assert_eq!(part2(&day12::Day12::default(), second), n(4_686_774_924));
+}
+
+#[test]
+fn day13() {
+    let solver = day13::Day13;
+    // A wall, two blocks and the paddle.
+    let screen = "104,0,104,0,104,1,104,1,104,0,104,2,104,2,104,0,104,2,104,1,104,1,104,3,99";
+    assert_eq!(part1(&solver, screen), n(2));
+
+    // Part 2 writes 2 to address 0, which here is a harmless multiplication.
+    let score = "2,50,50,50,104,-1,104,0,104,1234,99";
+    assert_eq!(part2(&solver, score), n(1234));
+}
+
+#[test]
+fn day14() {
+    let solver = day14::Day14;
+    let examples = [
+        (include_str!("data/day14_31.txt"), 31, None),
+        (include_str!("data/day14_165.txt"), 165, None),
+        (
+            include_str!("data/day14_13312.txt"),
+            13312,
+            Some(82_892_753),
+        ),
+        (
+            include_str!("data/day14_180697.txt"),
+            180_697,
+            Some(5_586_022),
+        ),
+        (
+            include_str!("data/day14_2210736.txt"),
+            2_210_736,
+            Some(460_664),
+        ),
+    ];
+
+    for &(input, ore, fuel) in examples.iter() {
+        assert_eq!(part1(&solver, input), n(ore));
+        if let Some(fuel) = fuel {
+            assert_eq!(part2(&solver, input), n(fuel));
+        }
+    }
+}
+
+#[test]
+fn day15() {
+    let solver = day15::Day15;
+    let maze = include_str!("data/day15_maze.txt");
+    assert_eq!(part1(&solver, maze), n(3));
+    assert_eq!(part2(&solver, maze), n(4));
+}
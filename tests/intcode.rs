@@ -0,0 +1,776 @@
+//! Conformance tests for the Intcode VM: every opcode in every parameter mode,
+//! the relative base, the error cases and the diagnostic programs from the
+//! puzzles.
+//!
+//! Each program runs on the plain interpreter, with statistics attached, which
+//! goes through `process_instruction_traced`, on the `FastMachine` and on the
+//! `BigMachine`, and all of them have to agree.
+
+extern crate advent_of_code_2019;
+extern crate num;
+
+use advent_of_code_2019::intcode::{
+    Arithmetic, BigMachine, Engine, FastMachine, IntCodeError, Machine, Memory, Stats, StepResult,
+};
+use num::{BigInt, ToPrimitive};
+
+const POSITION: i64 = 0;
+const IMMEDIATE: i64 = 1;
+const RELATIVE: i64 = 2;
+
+const MODES: [i64; 3] = [POSITION, IMMEDIATE, RELATIVE];
+const WRITE_MODES: [i64; 2] = [POSITION, RELATIVE];
+
+/// Every test program starts with `109, BASE`, so relative parameters
+/// addressing the data slots below it are negative.
+const BASE: i64 = 50;
+
+/// Addresses of the data slots for the first, second and third parameter.
+const SLOTS: [usize; 3] = [40, 41, 42];
+
+/// Address of the code a jump under test lands on.
+const TARGET: usize = 30;
+
+/// Size of every generated program, large enough to hold the slots.
+const SIZE: usize = 64;
+
+/// Runs `program` to completion with `inputs`, with and without statistics
+/// and on the other engines, and returns the outputs and the final machine.
+fn run(program: &[i64], inputs: &[i64]) -> Result<(Vec<i64>, Machine), IntCodeError> {
+    let plain = execute(program, inputs, false);
+    let traced = execute(program, inputs, true);
+    let fast = execute_fast(program, inputs);
+    let big = execute_big(program, inputs);
+
+    match (&plain, &traced) {
+        (Ok((out, machine)), Ok((traced_out, traced_machine))) => {
+            assert_eq!(out, traced_out, "traced outputs differ");
+            assert_eq!(machine.pc(), traced_machine.pc(), "traced pc differs");
+            assert_eq!(
+                machine.memory().to_vec(),
+                traced_machine.memory().to_vec(),
+                "traced memory differs"
+            );
+        }
+        (Err(err), Err(traced_err)) => {
+            assert_eq!(
+                err.to_string(),
+                traced_err.to_string(),
+                "traced error differs"
+            );
+        }
+        _ => panic!("traced run disagrees: {:?} vs {:?}", plain, traced),
+    }
+
+    match (&plain, &fast) {
+        (Ok((out, machine)), Ok((fast_out, fast_machine))) => {
+            assert_eq!(out, fast_out, "fast outputs differ");
+            assert_eq!(machine.pc(), fast_machine.pc(), "fast pc differs");
+            assert_eq!(
+                machine.memory().to_vec(),
+                fast_machine.to_memory().to_vec(),
+                "fast memory differs"
+            );
+        }
+        (Err(err), Err(fast_err)) => {
+            assert_eq!(err.to_string(), fast_err.to_string(), "fast error differs");
+        }
+        _ => panic!("fast run disagrees: {:?} vs {:?}", plain, fast),
+    }
+
+    // Only overflow sets the `BigMachine` apart.
+    match (&plain, &big) {
+        (Ok((out, machine)), Ok((big_out, big_machine))) => {
+            assert_eq!(out, &small(big_out), "big outputs differ");
+            assert_eq!(machine.pc(), big_machine.pc(), "big pc differs");
+            assert_eq!(
+                machine.memory().to_vec(),
+                small(&big_machine.to_vec()),
+                "big memory differs"
+            );
+        }
+        (Err(IntCodeError::Overflow { .. }), _) => {}
+        (Err(err), Err(big_err)) => {
+            assert_eq!(err.to_string(), big_err.to_string(), "big error differs");
+        }
+        _ => panic!("big run disagrees: {:?} vs {:?}", plain, big),
+    }
+
+    plain
+}
+
+fn execute(
+    program: &[i64],
+    inputs: &[i64],
+    stats: bool,
+) -> Result<(Vec<i64>, Machine), IntCodeError> {
+    let mut machine = Machine::new(program);
+    if stats {
+        machine.set_stats(Stats::new());
+    }
+    for &value in inputs {
+        machine.push_input(value);
+    }
+    machine.run()?;
+    Ok((machine.take_output(), machine))
+}
+
+fn execute_fast(program: &[i64], inputs: &[i64]) -> Result<(Vec<i64>, FastMachine), IntCodeError> {
+    let mut machine = FastMachine::new(program);
+    for &value in inputs {
+        machine.push_input(value);
+    }
+    machine.run()?;
+    Ok((machine.take_output(), machine))
+}
+
+fn execute_big(program: &[i64], inputs: &[i64]) -> Result<(Vec<BigInt>, BigMachine), IntCodeError> {
+    let mut machine = BigMachine::new(program);
+    for &value in inputs {
+        machine.push_input(value);
+    }
+    machine.run()?;
+    Ok((machine.take_output(), machine))
+}
+
+fn small(values: &[BigInt]) -> Vec<i64> {
+    values.iter().map(|v| v.to_i64().unwrap()).collect()
+}
+
+fn outputs(program: &[i64], inputs: &[i64]) -> Vec<i64> {
+    run(program, inputs).unwrap().0
+}
+
+/// A program of `SIZE` words that sets the relative base to `BASE` and then
+/// executes `code`, with the data slots filled in as parameters require.
+struct Builder {
+    memory: Vec<i64>,
+    code: Vec<i64>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Builder {
+            memory: vec![0; SIZE],
+            code: vec![109, BASE],
+        }
+    }
+
+    /// Appends an instruction with the given opcode and parameter modes.
+    fn op(&mut self, opcode: i64, modes: &[i64]) -> &mut Self {
+        let modes = modes
+            .iter()
+            .enumerate()
+            .map(|(i, &mode)| mode * 10_i64.pow(i as u32 + 2))
+            .sum::<i64>();
+        self.code.push(opcode + modes);
+        self
+    }
+
+    /// Appends a parameter reading `value` in `mode`, storing the value in the
+    /// data slot `n` unless it is immediate.
+    fn read(&mut self, n: usize, mode: i64, value: i64) -> &mut Self {
+        let slot = SLOTS[n];
+        let param = match mode {
+            IMMEDIATE => value,
+            _ => {
+                self.memory[slot] = value;
+                address_param(slot, mode)
+            }
+        };
+        self.code.push(param);
+        self
+    }
+
+    /// Appends a parameter writing to data slot `n` in `mode`.
+    fn write(&mut self, n: usize, mode: i64) -> &mut Self {
+        self.code.push(address_param(SLOTS[n], mode));
+        self
+    }
+
+    fn raw(&mut self, words: &[i64]) -> &mut Self {
+        self.code.extend_from_slice(words);
+        self
+    }
+
+    fn build(&self) -> Vec<i64> {
+        let mut memory = self.memory.clone();
+        memory[..self.code.len()].copy_from_slice(&self.code);
+        memory
+    }
+}
+
+fn address_param(slot: usize, mode: i64) -> i64 {
+    match mode {
+        RELATIVE => slot as i64 - BASE,
+        _ => slot as i64,
+    }
+}
+
+/// Runs a three-parameter instruction in every mode combination and checks
+/// the value written by it.
+fn check_binary(opcode: i64, cases: &[(i64, i64, i64)]) {
+    for &m1 in MODES.iter() {
+        for &m2 in MODES.iter() {
+            for &m3 in WRITE_MODES.iter() {
+                for &(a, b, expected) in cases {
+                    let program = Builder::new()
+                        .op(opcode, &[m1, m2, m3])
+                        .read(0, m1, a)
+                        .read(1, m2, b)
+                        .write(2, m3)
+                        .raw(&[4, SLOTS[2] as i64, 99])
+                        .build();
+                    assert_eq!(
+                        outputs(&program, &[]),
+                        vec![expected],
+                        "opcode {} modes {:?} with {} and {}",
+                        opcode,
+                        (m1, m2, m3),
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn add() {
+    check_binary(1, &[(3, 4, 7), (-5, 2, -3), (0, 0, 0)]);
+}
+
+#[test]
+fn multiply() {
+    check_binary(2, &[(3, 4, 12), (-5, 2, -10), (7, 0, 0)]);
+}
+
+#[test]
+fn less_than() {
+    check_binary(7, &[(3, 4, 1), (4, 4, 0), (5, 4, 0), (-1, 0, 1)]);
+}
+
+#[test]
+fn equals() {
+    check_binary(8, &[(4, 4, 1), (3, 4, 0), (-4, 4, 0)]);
+}
+
+#[test]
+fn input() {
+    for &mode in WRITE_MODES.iter() {
+        let program = Builder::new()
+            .op(3, &[mode])
+            .write(0, mode)
+            .raw(&[4, SLOTS[0] as i64, 99])
+            .build();
+        assert_eq!(outputs(&program, &[-17]), vec![-17], "mode {}", mode);
+    }
+}
+
+#[test]
+fn output() {
+    for &mode in MODES.iter() {
+        let program = Builder::new()
+            .op(4, &[mode])
+            .read(0, mode, 23)
+            .raw(&[99])
+            .build();
+        assert_eq!(outputs(&program, &[]), vec![23], "mode {}", mode);
+    }
+}
+
+/// Outputs 1 if the jump under test was taken and 0 if it fell through.
+fn check_jump(opcode: i64, taken_when: impl Fn(i64) -> bool) {
+    for &m1 in MODES.iter() {
+        for &m2 in MODES.iter() {
+            for &condition in [0, 1, -3].iter() {
+                let mut builder = Builder::new();
+                builder
+                    .op(opcode, &[m1, m2])
+                    .read(0, m1, condition)
+                    .read(1, m2, TARGET as i64)
+                    .raw(&[104, 0, 99]);
+                let mut program = builder.build();
+                program[TARGET..TARGET + 3].copy_from_slice(&[104, 1, 99]);
+
+                let expected = if taken_when(condition) { 1 } else { 0 };
+                assert_eq!(
+                    outputs(&program, &[]),
+                    vec![expected],
+                    "opcode {} modes {:?} with {}",
+                    opcode,
+                    (m1, m2),
+                    condition
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn jump_if_true() {
+    check_jump(5, |condition| condition != 0);
+}
+
+#[test]
+fn jump_if_false() {
+    check_jump(6, |condition| condition == 0);
+}
+
+#[test]
+fn relative_base_offset() {
+    for &mode in MODES.iter() {
+        for &offset in [-8, 0, 5].iter() {
+            // Moves the base by `offset`, then reads the slot the base points at.
+            let address = BASE + offset;
+            let program = {
+                let mut builder = Builder::new();
+                builder
+                    .op(9, &[mode])
+                    .read(0, mode, offset)
+                    .raw(&[204, 0, 99]);
+                let mut program = builder.build();
+                program[address as usize] = 1000 + offset;
+                program
+            };
+            let (out, machine) = run(&program, &[]).unwrap();
+            assert_eq!(out, vec![1000 + offset], "mode {} offset {}", mode, offset);
+            assert_eq!(machine.relative_base(), address);
+        }
+    }
+}
+
+#[test]
+fn relative_base_accumulates() {
+    // Base: 0 -> 10 -> 4 -> 7, then writes and reads back through it.
+    let program = [109, 10, 109, -6, 109, 3, 21101, 5, 6, -1, 204, -1, 99];
+    let (out, machine) = run(&program, &[]).unwrap();
+    assert_eq!(out, vec![11]);
+    assert_eq!(machine.relative_base(), 7);
+    assert_eq!(machine.memory().get(6), 11);
+}
+
+#[test]
+fn relative_base_can_go_negative() {
+    // The base may be negative as long as the addresses used are not.
+    let program = [109, -100, 204, 102, 99];
+    assert_eq!(outputs(&program, &[]), vec![204]);
+
+    let program = [109, -100, 204, 99, 99];
+    match run(&program, &[]) {
+        Err(IntCodeError::AddressOutOfRange { pc, address, .. }) => {
+            assert_eq!((pc, address), (2, -1));
+        }
+        other => panic!("expected AddressOutOfRange, got {:?}", other),
+    }
+}
+
+#[test]
+fn relative_addresses_past_i64_fail() {
+    // Below `i64::MIN` is negative for every engine.
+    let program = [109, i64::MIN, 204, -1, 99];
+    match run(&program, &[]) {
+        Err(IntCodeError::AddressOutOfRange { pc, address, .. }) => {
+            assert_eq!((pc, address), (2, i64::MIN));
+        }
+        other => panic!("expected AddressOutOfRange, got {:?}", other),
+    }
+
+    // Above `i64::MAX` is still an address to a `BigMachine`.
+    let program = [109, i64::MAX, 204, 1, 99];
+    let mut machine = Machine::new(&program[..]);
+    let mut fast = FastMachine::new(&program[..]);
+    for err in [machine.run().unwrap_err(), fast.run().unwrap_err()].iter() {
+        match err {
+            IntCodeError::AddressOutOfRange { pc, address, .. } => {
+                assert_eq!((*pc, *address), (2, i64::MAX));
+            }
+            other => panic!("expected AddressOutOfRange, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn memory_beyond_program() {
+    // Reads past the end are 0, writes past the end grow the memory.
+    let program = [4, 1000, 1101, 2, 3, 5000, 4, 5000, 99];
+    let (out, machine) = run(&program, &[]).unwrap();
+    assert_eq!(out, vec![0, 5]);
+    assert_eq!(machine.memory().len(), 5001);
+}
+
+#[test]
+fn far_writes_stay_small() {
+    // Writes far past the program, then copies code above the fast engine's
+    // flat memory and jumps to it.
+    let (far, high) = (16_000_000, 1 << 40);
+    let program = [
+        1101, 2, 3, far, // [far] = 5
+        4, far, // out [far]
+        109, high, 21101, 4, 5, 0, // rb = 2^40, [rb] = 9
+        204, 0, // out [rb]
+        1101, 0, 104, 100_000, // copy OUT #7, HLT to 100000
+        1101, 0, 7, 100_001, //
+        1101, 0, 99, 100_002, //
+        1105, 1, 100_000, // jmp 100000
+    ];
+
+    let mut machine = Machine::new(&program[..]);
+    machine.run().unwrap();
+    assert_eq!(machine.take_output(), vec![5, 9, 7]);
+    assert!(machine.memory().allocated_pages() <= 3);
+
+    let mut fast = FastMachine::new(&program[..]);
+    fast.run().unwrap();
+    assert_eq!(fast.take_output(), vec![5, 9, 7]);
+    assert_eq!(fast.get(far as usize), 5);
+    assert_eq!(fast.to_memory().len(), machine.memory().len());
+    assert!(fast.to_memory().allocated_pages() <= 3);
+
+    let mut big = BigMachine::new(&program);
+    big.run().unwrap();
+    assert_eq!(small(&big.take_output()), vec![5, 9, 7]);
+    assert_eq!(big.get(high as usize), BigInt::from(9));
+}
+
+#[test]
+fn copies_leave_out_sparse_words() {
+    let far = usize::MAX / 2;
+    let mut memory = Memory::new();
+    memory.set(5, 1);
+    memory.set(far, 7);
+    assert_eq!(memory.len(), far + 1);
+    // Up to the end of the one allocated page.
+    let words = memory.to_vec();
+    assert_eq!(words.len(), 1024);
+    assert_eq!(words[..7], [0, 0, 0, 0, 0, 1, 0]);
+    assert_eq!(memory.get(far), 7);
+
+    let mut big = BigMachine::new(&[0, 1]);
+    big.set(far, 7);
+    assert_eq!(big.to_vec().len(), 1024);
+    assert_eq!(big.get(far), BigInt::from(7));
+}
+
+#[test]
+fn self_modifying_code_across_flat_memory() {
+    // The fast engine keeps the first 2^16 words flat. An ADD at its last
+    // two words runs on above them, and is rewritten there after its first
+    // run.
+    let add = 65_534;
+    let (done, patch) = (add + 20, add + 2);
+    let code = [
+        1101, 7, 0, 100, // [100] = 7 + 0
+        4, 100, // out [100]
+        1005, 200, done, // done if [200]
+        1101, 0, 1, 200, // [200] = 1
+        1101, 0, 2, patch, // make it 7 + 2
+        1105, 1, add, // again
+        99,
+    ];
+    let mut program = vec![1105, 1, add];
+    program.resize(add as usize, 0);
+    program.extend(code.iter());
+
+    let (out, _) = run(&program, &[]).unwrap();
+    assert_eq!(out, vec![7, 9]);
+}
+
+#[test]
+fn immediate_writes_fail() {
+    let cases: [&[i64]; 5] = [
+        &[11101, 1, 2, 0, 99],
+        &[11102, 1, 2, 0, 99],
+        &[103, 0, 99],
+        &[11107, 1, 2, 0, 99],
+        &[11108, 1, 2, 0, 99],
+    ];
+    for program in cases.iter() {
+        // Behind a no-op so the failing pc isn't 0.
+        let mut code = vec![1101, 0, 0, 20];
+        code.extend(program.iter());
+        code.resize(21, 0);
+        let snapshot = code.clone();
+
+        match run(&code, &[1]) {
+            Err(IntCodeError::ImmediateWrite { pc, .. }) => assert_eq!(pc, 4, "{:?}", program),
+            other => panic!("expected ImmediateWrite for {:?}, got {:?}", program, other),
+        }
+
+        // Nothing was written before the error.
+        let mut machine = Machine::new(&snapshot[..]);
+        machine.push_input(1);
+        assert!(machine.step().is_ok());
+        assert!(machine.step().is_err());
+        assert_eq!(machine.pc(), 4);
+        assert_eq!(machine.memory().to_vec(), snapshot);
+    }
+}
+
+#[test]
+fn negative_addresses_fail() {
+    let cases: [(&[i64], i64); 4] = [
+        (&[4, -1, 99], -1),
+        (&[1, 0, 0, -5, 99], -5),
+        (&[109, -3, 204, 1, 99], -2),
+        (&[1105, 1, -7, 99], -7),
+    ];
+    for &(program, expected) in cases.iter() {
+        match run(program, &[]) {
+            Err(IntCodeError::AddressOutOfRange { address, .. }) => {
+                assert_eq!(address, expected, "{:?}", program)
+            }
+            other => panic!(
+                "expected AddressOutOfRange for {:?}, got {:?}",
+                program, other
+            ),
+        }
+    }
+}
+
+#[test]
+fn invalid_instructions_fail() {
+    match run(&[1101, 0, 0, 5, 42, 0], &[]) {
+        Err(IntCodeError::InvalidOpcode { pc, op }) => assert_eq!((pc, op), (4, 42)),
+        other => panic!("expected InvalidOpcode, got {:?}", other),
+    }
+
+    match run(&[301, 0, 0, 0, 99], &[]) {
+        Err(IntCodeError::InvalidMode { pc, op, mode }) => assert_eq!((pc, op, mode), (0, 301, 3)),
+        other => panic!("expected InvalidMode, got {:?}", other),
+    }
+
+    // A mode on the halt instruction is ignored, only the last two digits count.
+    assert_eq!(outputs(&[104, 1, 10099], &[]), vec![1]);
+}
+
+#[test]
+fn missing_input_fails() {
+    match run(&[104, 1, 3, 0, 99], &[]) {
+        Err(IntCodeError::InputExhausted { pc }) => assert_eq!(pc, 2),
+        other => panic!("expected InputExhausted, got {:?}", other),
+    }
+}
+
+#[test]
+fn self_modifying_code() {
+    // Day 2: the first instruction overwrites the halt with an addition.
+    let (_, machine) = run(&[1, 1, 1, 4, 99, 5, 6, 0, 99], &[]).unwrap();
+    assert_eq!(machine.memory().to_vec(), vec![30, 1, 1, 4, 2, 5, 6, 0, 99]);
+
+    // Day 5: 100 + -1 writes the halt that ends the program.
+    let (_, machine) = run(&[1101, 100, -1, 4, 0], &[]).unwrap();
+    assert_eq!(machine.memory().to_vec(), vec![1101, 100, -1, 4, 99]);
+    assert_eq!(machine.pc(), 5);
+
+    // An input patches the parameter of the next output instruction.
+    assert_eq!(outputs(&[3, 3, 104, 0, 99], &[-9]), vec![-9]);
+
+    // An instruction overwrites one of its own parameters; the value it read
+    // before the write is used.
+    let (out, machine) = run(&[1, 2, 2, 2, 4, 2, 99], &[]).unwrap();
+    assert_eq!(out, vec![4]);
+    assert_eq!(machine.memory().get(2), 4);
+
+    // A loop counts down by rewriting its own immediate operand.
+    let countdown = [
+        104, 3, // out 3          <- patched
+        1001, 1, -1, 1, // mem[1] -= 1
+        1005, 1, 0, // jnz mem[1], 0
+        99,
+    ];
+    assert_eq!(outputs(&countdown, &[]), vec![3, 2, 1]);
+}
+
+#[test]
+fn arithmetic_overflow() {
+    let max = i64::MAX;
+    let cases: [(&[i64], i64, &str); 3] = [
+        (
+            &[1101, max, 1, 7, 4, 7, 99, 0],
+            i64::MIN,
+            "9223372036854775808",
+        ),
+        (&[1102, max, 2, 7, 4, 7, 99, 0], -2, "18446744073709551614"),
+        (
+            &[1101, -max, -2, 7, 4, 7, 99, 0],
+            max,
+            "-9223372036854775809",
+        ),
+    ];
+    for &(program, wrapped, exact) in cases.iter() {
+        match run(program, &[]) {
+            Err(IntCodeError::Overflow { pc, .. }) => assert_eq!(pc, 0, "{:?}", program),
+            other => panic!("expected Overflow for {:?}, got {:?}", program, other),
+        }
+
+        let mut machine = Machine::new(program);
+        machine.set_arithmetic(Arithmetic::Wrapping);
+        machine.run().unwrap();
+        assert_eq!(machine.take_output(), vec![wrapped]);
+
+        let mut machine = FastMachine::new(program);
+        machine.set_arithmetic(Arithmetic::Wrapping);
+        machine.run().unwrap();
+        assert_eq!(machine.take_output(), vec![wrapped]);
+
+        let mut machine = BigMachine::new(program);
+        machine.run().unwrap();
+        assert_eq!(
+            machine.take_output(),
+            vec![exact.parse::<BigInt>().unwrap()]
+        );
+    }
+
+    // The relative base is checked too.
+    match run(&[109, max, 109, 1, 99], &[]) {
+        Err(IntCodeError::Overflow { pc, .. }) => assert_eq!(pc, 2),
+        other => panic!("expected Overflow, got {:?}", other),
+    }
+}
+
+#[test]
+fn halting_mid_stream() {
+    // Anything after the halt, even an invalid instruction, is never executed.
+    let program = [104, 1, 99, 104, 2, 42];
+    let (out, machine) = run(&program, &[5, 6]).unwrap();
+    assert_eq!(out, vec![1]);
+    assert!(machine.is_halted());
+    assert_eq!(machine.pc(), 3);
+
+    // A halted machine stays halted and keeps unread input.
+    let mut machine = Machine::new(&program[..]);
+    machine.push_input(5);
+    assert_eq!(machine.step().unwrap(), StepResult::Output(1));
+    assert_eq!(machine.step().unwrap(), StepResult::Halted);
+    assert_eq!(machine.step().unwrap(), StepResult::Halted);
+    assert_eq!(machine.run_until_output().unwrap(), None);
+    assert!(!machine.needs_input());
+    match machine.step_with(&mut || Ok(0), &mut |_| {}) {
+        Err(IntCodeError::Halted { pc }) => assert_eq!(pc, 3),
+        other => panic!("expected Halted, got {:?}", other),
+    }
+
+    // Halting between outputs: the consumer sees the outputs, then the end.
+    let mut machine = Machine::new(vec![3, 9, 4, 9, 1006, 9, 11, 104, 7, 0, 0, 99]);
+    machine.push_input(0);
+    assert_eq!(machine.run_until_output().unwrap(), Some(0));
+    assert_eq!(machine.run_until_output().unwrap(), None);
+    assert!(machine.is_halted());
+}
+
+#[test]
+fn fast_machine_pauses_for_input() {
+    // Echoes its input until it reads 0, decoding the loop only once.
+    let mut machine = FastMachine::new(vec![3, 12, 1006, 12, 10, 4, 12, 1105, 1, 0, 99, 0, 0]);
+    assert_eq!(
+        machine.run_until_input_needed().unwrap(),
+        StepResult::NeedInput
+    );
+    assert_eq!(machine.pc(), 0);
+
+    machine.push_input(5);
+    machine.push_input(6);
+    assert_eq!(
+        machine.run_until_input_needed().unwrap(),
+        StepResult::NeedInput
+    );
+    assert_eq!(machine.take_output(), vec![5, 6]);
+
+    machine.push_input(0);
+    assert_eq!(machine.run_until_output().unwrap(), None);
+    assert!(machine.is_halted());
+}
+
+#[test]
+fn day02_diagnostics() {
+    let (_, machine) = run(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50], &[]).unwrap();
+    assert_eq!(machine.memory().get(0), 3500);
+    assert_eq!(machine.memory().get(3), 70);
+
+    let cases: [(&[i64], &[i64]); 3] = [
+        (&[1, 0, 0, 0, 99], &[2, 0, 0, 0, 99]),
+        (&[2, 3, 0, 3, 99], &[2, 3, 0, 6, 99]),
+        (&[2, 4, 4, 5, 99, 0], &[2, 4, 4, 5, 99, 9801]),
+    ];
+    for &(program, expected) in cases.iter() {
+        let (_, machine) = run(program, &[]).unwrap();
+        assert_eq!(machine.memory().to_vec(), expected.to_vec());
+    }
+}
+
+#[test]
+fn day05_diagnostics() {
+    assert_eq!(outputs(&[3, 0, 4, 0, 99], &[42]), vec![42]);
+
+    let equal_position = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+    let less_position = [3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
+    let equal_immediate = [3, 3, 1108, -1, 8, 3, 4, 3, 99];
+    let less_immediate = [3, 3, 1107, -1, 8, 3, 4, 3, 99];
+    for &(input, equal, less) in [(7, 0, 1), (8, 1, 0), (9, 0, 0), (-8, 0, 1)].iter() {
+        assert_eq!(outputs(&equal_position, &[input]), vec![equal]);
+        assert_eq!(outputs(&equal_immediate, &[input]), vec![equal]);
+        assert_eq!(outputs(&less_position, &[input]), vec![less]);
+        assert_eq!(outputs(&less_immediate, &[input]), vec![less]);
+    }
+
+    let jump_position = [3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+    let jump_immediate = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+    for &(input, expected) in [(0, 0), (5, 1), (-5, 1)].iter() {
+        assert_eq!(outputs(&jump_position, &[input]), vec![expected]);
+        assert_eq!(outputs(&jump_immediate, &[input]), vec![expected]);
+    }
+
+    let compare = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+    for &(input, expected) in [(-3, 999), (7, 999), (8, 1000), (9, 1001), (80, 1001)].iter() {
+        assert_eq!(
+            outputs(&compare, &[input]),
+            vec![expected],
+            "input {}",
+            input
+        );
+    }
+}
+
+#[test]
+fn day09_diagnostics() {
+    let quine = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    assert_eq!(outputs(&quine, &[]), quine.to_vec());
+
+    assert_eq!(
+        outputs(&[1102, 34_915_192, 34_915_192, 7, 4, 7, 99, 0], &[]),
+        vec![1_219_070_632_396_864]
+    );
+    assert_eq!(
+        outputs(&[104, 1_125_899_906_842_624, 99], &[]),
+        vec![1_125_899_906_842_624]
+    );
+
+    // The relative-mode input the BOOST self-test checks for.
+    assert_eq!(outputs(&[109, 7, 203, 0, 204, 0, 99], &[1]), vec![1]);
+
+    // Squaring the 16 digit output once more only fits in a `BigMachine`.
+    let square = [
+        1102,
+        1_219_070_632_396_864,
+        1_219_070_632_396_864,
+        7,
+        4,
+        7,
+        99,
+        0,
+    ];
+    let mut machine = BigMachine::new(&square);
+    assert_eq!(
+        machine.run_until_output().unwrap().unwrap().to_string(),
+        "1486133206772489918753597034496"
+    );
+    assert!(run(&square, &[]).is_err());
+}
@@ -0,0 +1,131 @@
+//! The Intcode I/O adapters.
+
+extern crate advent_of_code_2019;
+extern crate crossbeam_channel;
+
+use advent_of_code_2019::intcode::{iter_input, Input, IntCodeError, Machine, Output};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::thread;
+
+/// Adds up input values until it reads a 0, outputting the running total.
+fn sum_program() -> Vec<i64> {
+    vec![
+        3, 20, // in  [20]
+        1006, 20, 14, // jz  [20], 14
+        1, 20, 21, 21, // add [20], [21] -> [21]
+        4, 21, // out [21]
+        1105, 1, 0, // jmp 0
+        99,
+    ]
+}
+
+fn run<I: Input + ?Sized, O: Output + ?Sized>(
+    program: &[i64],
+    input: &mut I,
+    output: &mut O,
+) -> Result<(), IntCodeError> {
+    Machine::new(program).run_io(input, output)
+}
+
+#[test]
+fn queues() {
+    let mut input: VecDeque<i64> = vec![1, 2, 3, 0].into_iter().collect();
+    let mut output = Vec::new();
+    run(&sum_program(), &mut input, &mut output).unwrap();
+    assert_eq!(output, vec![1, 3, 6]);
+    assert!(input.is_empty());
+
+    let mut queue = VecDeque::new();
+    run(&sum_program(), &mut iter_input(vec![4, 5, 0]), &mut queue).unwrap();
+    assert_eq!(queue, vec![4, 9]);
+}
+
+#[test]
+fn iterators() {
+    let mut output = Vec::new();
+    run(
+        &sum_program(),
+        &mut iter_input((1..=4).chain(Some(0))),
+        &mut output,
+    )
+    .unwrap();
+    assert_eq!(output, vec![1, 3, 6, 10]);
+
+    match run(&sum_program(), &mut iter_input(1..=2), &mut output) {
+        Err(IntCodeError::InputExhausted { pc }) => assert_eq!(pc, 0),
+        other => panic!("expected InputExhausted, got {:?}", other),
+    }
+}
+
+#[test]
+fn closures() {
+    let mut next = 0;
+    let mut input = || -> Result<i64, Box<dyn Error>> {
+        next += 1;
+        Ok(if next > 3 { 0 } else { next * 10 })
+    };
+    let mut seen = Vec::new();
+    run(&sum_program(), &mut input, &mut |value| seen.push(value)).unwrap();
+    assert_eq!(seen, vec![10, 30, 60]);
+
+    let mut failing = || -> Result<i64, Box<dyn Error>> { Err("sensor offline".into()) };
+    match run(&sum_program(), &mut failing, &mut Vec::new()) {
+        Err(IntCodeError::Input { pc, details }) => {
+            assert_eq!((pc, details.as_str()), (0, "sensor offline"))
+        }
+        other => panic!("expected an input error, got {:?}", other),
+    }
+}
+
+#[test]
+fn trait_objects() {
+    let mut input: Box<dyn Input> = Box::new(iter_input(vec![7, 8, 0]));
+    let mut output = Vec::new();
+    {
+        let sink: &mut dyn Output = &mut output;
+        run(&sum_program(), input.as_mut(), sink).unwrap();
+    }
+    assert_eq!(output, vec![7, 15]);
+}
+
+#[test]
+fn channels() {
+    // Two machines in a pipeline on their own threads: the second one adds
+    // up what the first one outputs.
+    let (to_first, first_in) = crossbeam_channel::unbounded();
+    let (first_out, second_in) = crossbeam_channel::unbounded();
+    let (second_out, results) = crossbeam_channel::unbounded();
+
+    let first = thread::spawn(move || {
+        let (mut input, mut output) = (first_in, first_out);
+        run(&sum_program(), &mut input, &mut output)
+    });
+    let second = thread::spawn(move || {
+        let (mut input, mut output) = (second_in, second_out);
+        run(&sum_program(), &mut input, &mut output)
+    });
+
+    for value in [1, 2, 3].iter() {
+        to_first.send(*value).unwrap();
+    }
+    // The first machine sees the end of its input once the sender is dropped.
+    drop(to_first);
+
+    match first.join().unwrap() {
+        Err(IntCodeError::InputExhausted { .. }) => {}
+        other => panic!("expected InputExhausted, got {:?}", other),
+    }
+    assert!(second.join().unwrap().is_err());
+    assert_eq!(results.iter().collect::<Vec<i64>>(), vec![1, 4, 10]);
+}
+
+#[test]
+fn closed_channel_fails_output() {
+    let (mut sender, receiver) = crossbeam_channel::unbounded();
+    drop(receiver);
+    match run(&[104, 1, 99], &mut iter_input(None), &mut sender) {
+        Err(IntCodeError::Output { pc, .. }) => assert_eq!(pc, 0),
+        other => panic!("expected an output error, got {:?}", other),
+    }
+}
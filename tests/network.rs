@@ -0,0 +1,151 @@
+//! Networks of Intcode machines.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::days::day07;
+use advent_of_code_2019::intcode::network::Network;
+use advent_of_code_2019::intcode::{IntCodeError, Machine};
+
+/// Reads a value, outputs it plus one and halts.
+const ADD_ONE: [i64; 10] = [3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+/// Reads a value, outputs it doubled and halts.
+const DOUBLE: [i64; 10] = [3, 9, 1002, 9, 2, 9, 4, 9, 99, 0];
+/// Reads two values, outputs their sum and halts.
+const SUM: [i64; 13] = [3, 11, 3, 12, 1, 11, 12, 11, 4, 11, 99, 0, 0];
+
+const FEEDBACK: [i64; 29] = [
+    3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28, 1005,
+    28, 6, 99, 0, 0, 5,
+];
+
+/// Runs `network` both ways, checks they agree and returns the network as
+/// left by the round-robin run.
+fn run_both(network: &Network) -> Network {
+    let mut round_robin = network.clone();
+    round_robin.run().unwrap();
+    let mut threaded = network.clone();
+    threaded.run_threaded().unwrap();
+
+    for id in network.nodes() {
+        assert_eq!(round_robin.outputs(id), threaded.outputs(id));
+    }
+    assert!(round_robin.is_halted() && threaded.is_halted());
+    round_robin
+}
+
+#[test]
+fn chain() {
+    let mut network = Network::chain(vec![
+        Machine::new(&ADD_ONE[..]),
+        Machine::new(&DOUBLE[..]),
+        Machine::new(&ADD_ONE[..]),
+    ]);
+    network.push_input(network.first(), 5);
+
+    let network = run_both(&network);
+    assert_eq!(network.outputs(network.first()), &[6]);
+    assert_eq!(network.last_output(network.last()), Some(13));
+    assert_eq!(
+        network.node("1").map(|id| network.outputs(id)),
+        Some(&[12][..])
+    );
+}
+
+#[test]
+fn graph() {
+    // in -> double -> sum
+    //   \-> add    -/
+    let mut network = Network::new();
+    let input = network.add("in", Machine::new(&ADD_ONE[..]));
+    let double = network.add("double", Machine::new(&DOUBLE[..]));
+    let add = network.add("add", Machine::new(&ADD_ONE[..]));
+    let sum = network.add("sum", Machine::new(&SUM[..]));
+    network.connect(input, double);
+    network.connect(input, add);
+    network.connect(double, sum);
+    network.connect(add, sum);
+    network.push_input(input, 5);
+
+    let network = run_both(&network);
+    assert_eq!(network.last_output(double), Some(12));
+    assert_eq!(network.last_output(add), Some(7));
+    assert_eq!(network.last_output(sum), Some(19));
+    assert_eq!(network.name(sum), "sum");
+    assert_eq!(network.node("sum"), Some(sum));
+    assert_eq!(network.node("missing"), None);
+}
+
+#[test]
+fn ring() {
+    let amplifiers = [9, 8, 7, 6, 5].iter().map(|&phase| {
+        let mut machine = Machine::new(&FEEDBACK[..]);
+        machine.push_input(phase);
+        machine
+    });
+    let mut network = Network::ring(amplifiers);
+    network.push_input(network.first(), 0);
+
+    let network = run_both(&network);
+    assert_eq!(network.last_output(network.last()), Some(139_629_729));
+    // Every amplifier goes around the feedback loop five times.
+    assert_eq!(network.outputs(network.first()).len(), 5);
+
+    assert_eq!(
+        day07::amplify_threaded(&FEEDBACK, &[9, 8, 7, 6, 5]).unwrap(),
+        139_629_729
+    );
+}
+
+#[test]
+fn seeds_come_first() {
+    // The source outputs straight away, while the sink's own input is still
+    // to be queued behind the nodes started in between.
+    for _ in 0..20 {
+        let mut network = Network::new();
+        let source = network.add("source", Machine::new(vec![104, 7, 99]));
+        for n in 0..30 {
+            network.add(format!("idle {}", n), Machine::new(vec![99]));
+        }
+        let sink = network.add(
+            "sink",
+            Machine::new(vec![3, 11, 3, 12, 4, 11, 4, 12, 99, 0, 0, 0]),
+        );
+        network.machine_mut(sink).push_input(1);
+        network.connect(source, sink);
+
+        let network = run_both(&network);
+        assert_eq!(network.outputs(sink), &[1, 7][..]);
+    }
+}
+
+#[test]
+fn deadlock() {
+    // Two machines waiting for each other.
+    let mut network = Network::ring(vec![Machine::new(&DOUBLE[..]), Machine::new(&DOUBLE[..])]);
+    match network.run() {
+        Err(IntCodeError::InputExhausted { pc }) => assert_eq!(pc, 0),
+        other => panic!("expected InputExhausted, got {:?}", other),
+    }
+
+    // Nothing ever reaches the chain when run on threads either.
+    let mut network = Network::chain(vec![Machine::new(&DOUBLE[..]), Machine::new(&DOUBLE[..])]);
+    match network.run_threaded() {
+        Err(IntCodeError::InputExhausted { .. }) => {}
+        other => panic!("expected InputExhausted, got {:?}", other),
+    }
+}
+
+#[test]
+fn errors_are_reported() {
+    let mut network = Network::chain(vec![Machine::new(vec![104, 1, 99]), Machine::new(vec![42])]);
+    assert!(matches!(
+        network.run(),
+        Err(IntCodeError::InvalidOpcode { op: 42, .. })
+    ));
+
+    let mut network = Network::chain(vec![Machine::new(vec![104, 1, 99]), Machine::new(vec![42])]);
+    assert!(matches!(
+        network.run_threaded(),
+        Err(IntCodeError::InvalidOpcode { op: 42, .. })
+    ));
+}
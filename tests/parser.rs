@@ -0,0 +1,104 @@
+//! The typed input parsers and the locations they report.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::days::{day03, day12};
+use advent_of_code_2019::parser::{self, ParseError};
+use advent_of_code_2019::solver::{Solver, SolverError};
+use advent_of_code_2019::wires::{self, Move};
+
+fn location(err: ParseError) -> (usize, usize) {
+    (err.line, err.column)
+}
+
+#[test]
+fn comma_list() {
+    assert_eq!(
+        parser::parse_comma_list::<i64>("1,-2, 3 ,4,\n").unwrap(),
+        vec![1, -2, 3, 4]
+    );
+    assert_eq!(
+        parser::parse_comma_list::<i64>("1\n2\n").unwrap(),
+        vec![1, 2]
+    );
+
+    let err = parser::parse_comma_list::<i64>("1,2,x3").unwrap_err();
+    assert_eq!(location(err), (1, 5));
+    let err = parser::parse_comma_list::<i64>("1,2\n3,  4a").unwrap_err();
+    assert_eq!(location(err.clone()), (2, 5));
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 5: invalid value '4a': invalid digit found in string"
+    );
+}
+
+#[test]
+fn lines() {
+    assert_eq!(
+        parser::parse_lines::<u32>("12\n\n  14\n").unwrap(),
+        vec![12, 14]
+    );
+    let err = parser::parse_lines::<u32>("12\n\n  -14\n").unwrap_err();
+    assert_eq!(location(err), (3, 3));
+}
+
+#[test]
+fn digits() {
+    assert_eq!(
+        parser::parse_digits("123\n45\n").unwrap(),
+        vec![1, 2, 3, 4, 5]
+    );
+    let err = parser::parse_digits("123\n4.5").unwrap_err();
+    assert_eq!(location(err), (2, 2));
+}
+
+#[test]
+fn grid() {
+    assert_eq!(
+        parser::parse_grid::<u8>("12\n34\n").unwrap(),
+        vec![vec![1, 2], vec![3, 4]]
+    );
+    let err = parser::parse_grid::<u8>("12\n3x\n").unwrap_err();
+    assert_eq!(location(err), (2, 2));
+    let err = parser::parse_grid::<u8>("12\n34\n5\n").unwrap_err();
+    assert_eq!(location(err), (3, 2));
+}
+
+#[test]
+fn records() {
+    let parsed = parser::parse_records("a=1\n\n b=2\n", |line| {
+        let i = line
+            .find('=')
+            .ok_or_else(|| ParseError::new(1, "missing '='"))?;
+        let value = line[i + 1..]
+            .parse::<i32>()
+            .map_err(|e| ParseError::new(i + 2, e.to_string()))?;
+        Ok((&line[..i], value))
+    })
+    .unwrap();
+    assert_eq!(parsed, vec![("a", 1), ("b", 2)]);
+
+    // Columns count from the start of the untrimmed line.
+    let err = parser::parse_records("1,2\n  3,x\n", parser::parse_comma_list::<i32>).unwrap_err();
+    assert_eq!(location(err), (2, 5));
+}
+
+#[test]
+fn puzzle_inputs() {
+    let moves = wires::parse_wires("R8,U5\nL5,D3\n").unwrap();
+    assert_eq!(moves.len(), 2);
+    assert!(matches!(moves[0][1], Move::Up(5)));
+
+    let err = wires::parse_wires("R8,U5\nL5,X3\n").unwrap_err();
+    assert_eq!(location(err), (2, 4));
+
+    match day03::Day03.part1("R8,U5\nL5,D") {
+        Err(SolverError::Parse(msg)) => assert!(msg.starts_with("line 2, column 4"), "{}", msg),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    match day12::Day12::default().part1("<x=1, y=2, z=3>\n<x=1, y=two, z=3>") {
+        Err(SolverError::Parse(msg)) => assert!(msg.starts_with("line 2, column 9"), "{}", msg),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
@@ -0,0 +1,134 @@
+//! Loading Intcode programs in the formats we exchange them in.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{self, Engine, LoadError};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const PROGRAM: [i64; 6] = [1002, 4, 3, 4, 33, -1_125_899_906_842_624];
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = env::temp_dir().join(format!("intcode-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn text_formats() {
+    let formats = [
+        "1002,4,3,4,33,-1125899906842624\n",
+        "1002,4,3,4,33,-1125899906842624",
+        "1002 4 3 4\t33 -1125899906842624",
+        "1002\n4\n3\n4\n33\n-1125899906842624\n",
+        "1002, 4, 3, 4,\n33, -1125899906842624,\n\n",
+        "# multiply 33 by 3\n1002,4,3,4 # into 4\n  # data\n33,-1125899906842624\n",
+    ];
+    for text in formats.iter() {
+        assert_eq!(intcode::parse_program(text).unwrap(), PROGRAM, "{:?}", text);
+    }
+    assert_eq!(intcode::parse_program("").unwrap(), Vec::<i64>::new());
+    assert_eq!(
+        intcode::parse_program("# nothing\n").unwrap(),
+        Vec::<i64>::new()
+    );
+}
+
+#[test]
+fn bad_tokens() {
+    let err = intcode::parse_program("# header\n1,2\n3 four 5").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 3));
+
+    let err = intcode::parse_program("1,2,3.5").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 5));
+
+    match intcode::decode_program(b"1,2,x") {
+        Err(LoadError::Parse(err)) => assert_eq!(err.column, 5),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn binary_format() {
+    let bytes = intcode::encode_program(&PROGRAM);
+    assert_eq!(bytes.len(), 8 + PROGRAM.len() * 8);
+    assert_eq!(&bytes[..8], intcode::BINARY_MAGIC);
+    assert_eq!(&bytes[8..16], &1002_i64.to_le_bytes());
+    assert_eq!(intcode::decode_program(&bytes).unwrap(), PROGRAM);
+
+    // Words whose bytes all happen to be printable are still binary.
+    let printable = [0x3131_3131_3131_3131, 0x2c31_3131_3131_3131, 0];
+    let bytes = intcode::encode_program(&printable);
+    assert_eq!(intcode::decode_program(&bytes).unwrap(), printable);
+    assert_eq!(intcode::decode_program(&[]).unwrap(), Vec::<i64>::new());
+    let empty = intcode::encode_program(&[]);
+    assert_eq!(intcode::decode_program(&empty).unwrap(), Vec::<i64>::new());
+
+    match intcode::decode_program(&bytes[..11]) {
+        Err(LoadError::Binary { len }) => assert_eq!(len, 3),
+        other => panic!("expected a binary error, got {:?}", other),
+    }
+    // Without the header the bytes are text, up to the zero word.
+    match intcode::decode_program(&bytes[8..]) {
+        Err(LoadError::Parse(err)) => assert_eq!((err.line, err.column), (1, 17)),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+    match intcode::decode_program(b"1,2\n3,\xff") {
+        Err(LoadError::Parse(err)) => assert_eq!((err.line, err.column), (2, 3)),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn headerless_binary() {
+    let raw: Vec<u8> = PROGRAM.iter().flat_map(|w| w.to_le_bytes()).collect();
+    assert_eq!(intcode::decode_binary(&raw).unwrap(), PROGRAM);
+    match intcode::decode_binary(&raw[..13]) {
+        Err(LoadError::Binary { len }) => assert_eq!(len, 13),
+        other => panic!("expected a binary error, got {:?}", other),
+    }
+
+    // A `.bin` file is binary whatever it starts with.
+    let bin = temp_file("raw.bin", &raw);
+    assert_eq!(intcode::load_program(&bin).unwrap(), PROGRAM);
+
+    // Any other file needs `--binary`.
+    let dat = temp_file("raw.dat", &raw);
+    let args: Vec<String> = ["prog", "--binary", "-f", dat.to_str().unwrap(), "5"]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    let (program, extra) = intcode::program_from_args(&args, None).unwrap();
+    assert_eq!(program, PROGRAM);
+    assert_eq!(extra, ["5"]);
+    let without_flag = [args[0].clone(), args[2].clone(), args[3].clone()];
+    assert!(intcode::program_from_args(&without_flag, None).is_err());
+
+    fs::remove_file(bin).unwrap();
+    fs::remove_file(dat).unwrap();
+}
+
+#[test]
+fn load_files() {
+    let text = temp_file("text.txt", b"# test\n1002,4,3,4,33,-1125899906842624\n");
+    let binary = temp_file("binary.bin", &intcode::encode_program(&PROGRAM));
+
+    assert_eq!(intcode::load_program(&text).unwrap(), PROGRAM);
+    assert_eq!(intcode::load_program(&binary).unwrap(), PROGRAM);
+
+    // Both run the same.
+    for path in [&text, &binary].iter() {
+        let mut machine = intcode::Machine::new(intcode::load_program(path).unwrap());
+        machine.run().unwrap();
+        assert_eq!(machine.memory().get(4), 99);
+    }
+
+    fs::remove_file(text).unwrap();
+    fs::remove_file(binary).unwrap();
+
+    match intcode::load_program(env::temp_dir().join("intcode-missing-program")) {
+        Err(LoadError::Input(_)) => {}
+        other => panic!("expected an input error, got {:?}", other),
+    }
+}
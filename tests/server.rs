@@ -0,0 +1,117 @@
+//! Serving machines over sockets, tested with loopback clients.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::server::Server;
+use advent_of_code_2019::intcode::{IntCodeError, Machine};
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+/// Adds up input values until it reads a 0, outputting the running total.
+const SUM: [i64; 15] = [3, 20, 1006, 20, 14, 1, 20, 21, 21, 4, 21, 1105, 1, 0, 99];
+
+fn server() -> Server {
+    Server::new(Machine::new(&SUM[..]))
+}
+
+/// Sends `line` and returns the line the server answers with.
+fn send<S: Read + Write>(stream: &mut BufReader<S>, line: &str) -> String {
+    writeln!(stream.get_mut(), "{}", line).unwrap();
+    let mut answer = String::new();
+    stream.read_line(&mut answer).unwrap();
+    answer.trim_end().to_string()
+}
+
+fn rest<S: Read>(stream: &mut BufReader<S>) -> String {
+    let mut rest = String::new();
+    stream.read_to_string(&mut rest).unwrap();
+    rest
+}
+
+#[test]
+fn tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || server().serve_tcp(&listener));
+
+    // Every connection gets a machine of its own.
+    let mut first = BufReader::new(TcpStream::connect(address).unwrap());
+    let mut second = BufReader::new(TcpStream::connect(address).unwrap());
+    assert_eq!(send(&mut first, "1"), "1");
+    assert_eq!(send(&mut second, "10"), "10");
+    assert_eq!(send(&mut first, "2"), "3");
+    // Blank lines are skipped.
+    assert_eq!(send(&mut second, "\n-4"), "6");
+
+    // Halting closes the connection.
+    writeln!(first.get_mut(), "0").unwrap();
+    assert_eq!(rest(&mut first), "");
+
+    assert_eq!(
+        send(&mut second, "x"),
+        "error: Input failed at 0: invalid digit found in string"
+    );
+    assert_eq!(rest(&mut second), "");
+}
+
+#[cfg(unix)]
+#[test]
+fn unix() {
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let path = std::env::temp_dir().join(format!("intcode-server-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).unwrap();
+    thread::spawn(move || server().serve_unix(&listener));
+
+    let mut client = BufReader::new(UnixStream::connect(&path).unwrap());
+    assert_eq!(send(&mut client, "5"), "5");
+    assert_eq!(send(&mut client, "7"), "12");
+    writeln!(client.get_mut(), "0").unwrap();
+    assert_eq!(rest(&mut client), "");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn disconnect() {
+    let mut output = Vec::new();
+    match server().handle(&b"1\n2\n"[..], &mut output) {
+        Err(IntCodeError::InputExhausted { pc }) => assert_eq!(pc, 0),
+        other => panic!("expected InputExhausted, got {:?}", other),
+    }
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .starts_with("1\n3\nerror: "));
+}
+
+/// Accepts one line, then fails like a socket whose client has gone.
+struct Hangup(usize);
+
+impl Write for Hangup {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.0 {
+            0 => Err(io::Error::new(io::ErrorKind::BrokenPipe, "hung up")),
+            _ => {
+                self.0 -= buf.iter().filter(|&&b| b == b'\n').count();
+                Ok(buf.len())
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn hangup() {
+    match server().handle(&b"1\n2\n3\n"[..], Hangup(1)) {
+        Err(IntCodeError::Output { pc, details }) => {
+            assert_eq!(pc, 9);
+            assert_eq!(details, "hung up");
+        }
+        other => panic!("expected an output error, got {:?}", other),
+    }
+}
@@ -0,0 +1,89 @@
+//! Saving and restoring machines.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{Arithmetic, Engine, IntCodeError, Machine, Memory, StepResult};
+
+fn reload(machine: &Machine) -> Machine {
+    let mut bytes = Vec::new();
+    machine.save(&mut bytes).unwrap();
+    Machine::load(&mut &bytes[..]).unwrap()
+}
+
+#[test]
+fn memory_round_trip() {
+    let mut memory = Memory::new();
+    for (address, value) in [(0, 1), (5, -7), (3000, 42), (1 << 30, i64::MIN)] {
+        memory.set(address, value);
+    }
+
+    let mut bytes = Vec::new();
+    memory.write_to(&mut bytes).unwrap();
+    let copy = Memory::read_from(&mut &bytes[..]).unwrap();
+    assert_eq!(copy.len(), memory.len());
+    assert_eq!(copy.allocated_pages(), memory.allocated_pages());
+    assert_eq!(copy.sparse_cells(), 1);
+    assert_eq!(copy.get(5), -7);
+    assert_eq!(copy.get(3000), 42);
+    assert_eq!(copy.get(1 << 30), i64::MIN);
+    assert_eq!(copy.get(4), 0);
+}
+
+#[test]
+fn resume_after_reload() {
+    // Adds up input values until it reads a 0, outputting the running total.
+    let program = [3, 20, 1006, 20, 14, 1, 20, 21, 21, 4, 21, 1105, 1, 0, 99];
+
+    let mut machine = Machine::new(&program[..]);
+    machine.push_input(3);
+    machine.push_input(4);
+    assert_eq!(
+        machine.run_until_input_needed().unwrap(),
+        StepResult::NeedInput
+    );
+    assert_eq!(machine.take_output(), vec![3, 7]);
+
+    // Pending input is part of the snapshot too.
+    machine.push_input(5);
+    let mut copy = reload(&machine);
+    assert_eq!(copy.memory().to_vec(), machine.memory().to_vec());
+    copy.push_input(0);
+    copy.run().unwrap();
+    assert_eq!(copy.take_output(), vec![12]);
+    assert_eq!(copy.run_until_input_needed().unwrap(), StepResult::Halted);
+}
+
+#[test]
+fn arithmetic_survives_snapshots() {
+    // Reads a value and adds it to i64::MAX.
+    let program = [3, 9, 1001, 9, i64::MAX, 9, 4, 9, 99, 0];
+
+    let mut machine = Machine::new(&program[..]);
+    machine.set_arithmetic(Arithmetic::Wrapping);
+    assert_eq!(
+        machine.run_until_input_needed().unwrap(),
+        StepResult::NeedInput
+    );
+    let mut machine = reload(&machine);
+    assert_eq!(machine.arithmetic(), Arithmetic::Wrapping);
+    machine.push_input(1);
+    machine.run().unwrap();
+    assert_eq!(machine.take_output(), vec![i64::MIN]);
+
+    let mut machine = reload(&Machine::new(&program[..]));
+    assert_eq!(machine.arithmetic(), Arithmetic::Checked);
+    machine.push_input(1);
+    assert!(matches!(
+        machine.run(),
+        Err(IntCodeError::Overflow { pc: 2, .. })
+    ));
+}
+
+#[test]
+fn old_snapshots_are_rejected() {
+    let mut bytes = Vec::new();
+    Machine::new(vec![99]).save(&mut bytes).unwrap();
+    bytes[4] = 1;
+    let err = Machine::load(&mut &bytes[..]).unwrap_err();
+    assert_eq!(err.to_string(), "Unsupported snapshot version 1");
+}
@@ -0,0 +1,69 @@
+//! Execution statistics.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{Engine, Machine, Stats};
+use std::collections::HashMap;
+
+/// Reads n, then outputs n, n - 1, ..., 1.
+const COUNTDOWN: [i64; 12] = [3, 100, 4, 100, 1001, 100, -1, 100, 1005, 100, 2, 99];
+
+fn countdown(n: i64) -> Stats {
+    let mut machine = Machine::new(&COUNTDOWN[..]);
+    machine.set_stats(Stats::new());
+    machine.push_input(n);
+    machine.run().unwrap();
+    assert_eq!(machine.take_output().len() as i64, n);
+    machine.take_stats().unwrap()
+}
+
+#[test]
+fn counts() {
+    let stats = countdown(3);
+    assert_eq!(stats.instructions, 11);
+    assert_eq!((stats.inputs, stats.outputs), (1, 3));
+    assert_eq!(stats.peak_address, 100);
+
+    let opcodes: HashMap<&str, u64> = [("IN", 1), ("OUT", 3), ("ADD", 3), ("JT", 3), ("HLT", 1)]
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(stats.opcodes, opcodes);
+    assert_eq!(
+        stats.hotspots(10),
+        vec![(2, 3), (4, 3), (8, 3), (0, 1), (11, 1)]
+    );
+    assert_eq!(stats.hotspots(2), vec![(2, 3), (4, 3)]);
+
+    let mut total = countdown(1);
+    total.merge(&stats);
+    assert_eq!(total.instructions, 5 + 11);
+    assert_eq!(total.opcodes["OUT"], 1 + 3);
+    assert_eq!(total.hits[&0], 2);
+}
+
+#[test]
+fn summary() {
+    assert_eq!(
+        countdown(3).to_string().lines().collect::<Vec<_>>(),
+        [
+            "Instructions executed: 11",
+            "Inputs: 1, outputs: 3",
+            "Peak address: 100",
+            "",
+            "Opcode         Count       %",
+            "ADD                3    27.3",
+            "JT                 3    27.3",
+            "OUT                3    27.3",
+            "HLT                1     9.1",
+            "IN                 1     9.1",
+            "",
+            "Address         Hits       %",
+            "2                  3    27.3",
+            "4                  3    27.3",
+            "8                  3    27.3",
+            "0                  1     9.1",
+            "11                 1     9.1",
+        ]
+    );
+}
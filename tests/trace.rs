@@ -0,0 +1,73 @@
+//! Tracing executed instructions.
+
+extern crate advent_of_code_2019;
+
+use advent_of_code_2019::intcode::{Engine, FileTracer, IntCodeError, Machine, TraceEvent, Tracer};
+use std::env;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+/// Adds 5 to the word at rb+1 into rb+2 and outputs it, then fails on an
+/// invalid opcode.
+const PROGRAM: [i64; 13] = [109, 10, 21201, 1, 5, 2, 204, 2, 98, 0, 0, 7, 0];
+
+/// Collects events where the test can still get at them.
+struct Collect(Arc<Mutex<Vec<TraceEvent>>>);
+
+impl Tracer for Collect {
+    fn trace(&mut self, event: &TraceEvent) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
+
+fn failed(machine: &mut Machine) {
+    match machine.run() {
+        Err(IntCodeError::InvalidOpcode { pc, op }) => assert_eq!((pc, op), (8, 98)),
+        other => panic!("expected InvalidOpcode, got {:?}", other),
+    }
+}
+
+#[test]
+fn events() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut machine = Machine::new(&PROGRAM[..]);
+    machine.set_tracer(Box::new(Collect(events.clone())));
+    failed(&mut machine);
+
+    let events = events.lock().unwrap();
+    let fields: Vec<_> = events
+        .iter()
+        .map(|e| {
+            let name = e.instruction.mnemonic();
+            (e.pc, e.relative_base, name, e.operands.clone(), e.write)
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            (0, 0, "ARB", vec![10], None),
+            (2, 10, "ADD", vec![7, 5], Some((12, 12))),
+            (6, 10, "OUT", vec![12], None),
+        ]
+    );
+}
+
+#[test]
+fn file_lines() {
+    let path = env::temp_dir().join(format!("intcode-trace-{}.log", std::process::id()));
+    let mut machine = Machine::new(&PROGRAM[..]);
+    machine.set_tracer(Box::new(FileTracer::create(&path).unwrap()));
+    failed(&mut machine);
+
+    // The machine, and with it the tracer, is still alive.
+    let trace = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        trace.lines().collect::<Vec<_>>(),
+        [
+            "     0 rb=0      ARB  #10                         (10)",
+            "     2 rb=10     ADD  rb+1, #5, rb+2              (7, 5) [12] <- 12",
+            "     6 rb=10     OUT  rb+2                        (12)",
+        ]
+    );
+}